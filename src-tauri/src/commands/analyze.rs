@@ -1,7 +1,8 @@
-use crate::models::{ClipSuggestion, ValidatedClip};
-use crate::services::{VttParser, OpenAIService};
-use crate::commands::settings::get_api_key;
-use tauri::{command, AppHandle};
+use crate::models::{AnalysisSettings, ClipSuggestion, Timestamp, ValidatedClip};
+use crate::services::{VttParser, OpenAIService, TranscriptLoader, HttpClient, MediaInfoService, provider_from_settings};
+use crate::commands::settings::load_settings;
+use std::time::Duration;
+use tauri::{command, AppHandle, Manager};
 
 // Need Clone for ClipSuggestion in validation logging
 
@@ -9,37 +10,55 @@ use tauri::{command, AppHandle};
 pub async fn analyze_transcript_for_clips(
     app_handle: AppHandle,
     transcript_path: String,
-    _video_path: String,
+    video_path: String,
     user_context: Option<String>,
 ) -> Result<Vec<ValidatedClip>, String> {
-    // 1. Get API key
-    let api_key = get_api_key(app_handle).await?
-        .ok_or("No API key configured. Please add your OpenAI API key in Settings.")?;
+    // 1. Build the configured LLM provider (OpenAI or a local compatible server)
+    let settings = load_settings(&app_handle)?;
+    let retry_handle = app_handle.clone();
+    let http = HttpClient::new(&settings.network)?
+        .with_retry_listener(move |event| {
+            let _ = retry_handle.emit_all("analysis-progress", event.to_string());
+        });
+    let provider = provider_from_settings(&settings.analysis, settings.openai_api_key.clone(), http)?;
     
-    // 2. Parse VTT file
-    let vtt_cues = VttParser::parse(&transcript_path)?;
+    // 2. Parse transcript file (WebVTT or SRT, detected automatically)
+    let vtt_cues = TranscriptLoader::load(&transcript_path)?;
     
-    // 3. Format VTT with full structure (timestamps, cue numbers) for GPT-5
-    let formatted_vtt = VttParser::get_formatted_vtt(&vtt_cues);
+    let speakers = VttParser::get_speakers(&vtt_cues);
+    let language = TranscriptLoader::load_language(&transcript_path);
     
     println!("=== Analyzing Transcript ===");
     println!("VTT cues: {}", vtt_cues.len());
+    println!("Speakers: {}", if speakers.is_empty() { "None".to_string() } else { speakers.join(", ") });
+    println!("Language: {}", language.as_deref().unwrap_or("Unknown"));
     println!("User context: {}", user_context.as_deref().unwrap_or("None"));
     
-    // 4. Call OpenAI GPT-5-mini
-    let raw_clips = OpenAIService::analyze_transcript(
-        &api_key,
-        &formatted_vtt,
+    // 3. Ask the model for clip suggestions (long transcripts are analyzed in windows)
+    let raw_clips = OpenAIService::analyze_cues(
+        provider.as_ref(),
+        &vtt_cues,
         user_context.as_deref(),
+        language.as_deref(),
+        &settings.analysis,
     ).await?;
     
-    // 5. Validate and map timestamps to actual VTT cues
+    // 4. Validate and map timestamps to actual VTT cues, keeping clips inside
+    //    the video (transcripts can run past it, e.g. one made from another cut)
     println!("=== Validating {} Suggested Clips ===", raw_clips.len());
     
+    let video_duration = match MediaInfoService::probe(&video_path).await {
+        Ok(info) => Some(info.duration),
+        Err(e) => {
+            println!("⚠️  Could not probe video, skipping duration check: {}", e);
+            None
+        }
+    };
+    
     let validated_clips: Vec<ValidatedClip> = raw_clips
         .into_iter()
         .filter_map(|clip| {
-            let result = validate_and_map_clip(clip.clone(), &vtt_cues);
+            let result = validate_and_map_clip(clip.clone(), &vtt_cues, video_duration, &settings.analysis);
             if result.is_none() {
                 println!("⚠️  Rejected clip: {} ({} -> {})", clip.title, clip.start_time, clip.end_time);
             }
@@ -55,24 +74,57 @@ pub async fn analyze_transcript_for_clips(
 fn validate_and_map_clip(
     clip: ClipSuggestion,
     vtt_cues: &[crate::models::VttCue],
+    video_duration: Option<Timestamp>,
+    analysis: &AnalysisSettings,
 ) -> Option<ValidatedClip> {
-    // Find the closest VTT cues for start and end times
-    let start_cue = VttParser::find_closest_cue(vtt_cues, &clip.start_time)?;
-    let end_cue = VttParser::find_closest_cue(vtt_cues, &clip.end_time)?;
+    // Find the cue starting closest to the start and the one ending closest to the end
+    let start_cue = VttParser::find_closest_cue(vtt_cues, clip.start_time)?;
+    let end_cue = VttParser::find_closest_cue_end(vtt_cues, clip.end_time)?;
+    
+    // Within those cues, snap to the word nearest the suggested time when word
+    // timings exist, otherwise to the cue edge (full millisecond precision either way)
+    let mut start_time = VttParser::find_closest_word_start(start_cue, clip.start_time)
+        .unwrap_or(start_cue.start_timestamp);
+    let mut end_time = VttParser::find_closest_word_end(end_cue, clip.end_time)
+        .unwrap_or(end_cue.end_timestamp);
+    
+    // Don't start or stop mid-sentence
+    if analysis.snap_to_sentences {
+        let max_shift = Duration::from_secs(analysis.sentence_snap_seconds as u64);
+        (start_time, end_time) = VttParser::snap_to_sentences(vtt_cues, start_time, end_time, max_shift);
+    }
     
-    // Convert to FFmpeg format (remove milliseconds)
-    let start_time = VttParser::vtt_to_ffmpeg_timestamp(&start_cue.start_timestamp);
-    let end_time = VttParser::vtt_to_ffmpeg_timestamp(&end_cue.end_timestamp);
+    // Trim clips that run past the end of the video
+    if let Some(duration) = video_duration {
+        if end_time > duration {
+            println!("⚠️  Clip '{}' ends after the video ({} > {}), trimming", clip.title, end_time, duration);
+            end_time = duration;
+        }
+    }
     
     // Verify end is after start
-    if VttParser::timestamp_to_seconds(&end_time)? 
-        <= VttParser::timestamp_to_seconds(&start_time)? {
+    if end_time <= start_time {
         return None;
     }
     
+    // Pad both ends, without going before the start or past the end of the video
+    start_time = start_time - Duration::from_millis(analysis.pre_roll_ms);
+    end_time = end_time + Duration::from_millis(analysis.post_roll_ms);
+    if let Some(duration) = video_duration {
+        end_time = end_time.min(duration);
+    }
+    
     // Sanitize filename
     let sanitized_filename = sanitize_filename(&clip.title);
     
+    // Speakers heard within the clip
+    let clip_cues: Vec<_> = vtt_cues
+        .iter()
+        .filter(|cue| cue.start_timestamp < end_time && cue.end_timestamp > start_time)
+        .cloned()
+        .collect();
+    let speakers = VttParser::get_speakers(&clip_cues);
+    
     Some(ValidatedClip {
         id: uuid::Uuid::new_v4().to_string(),
         title: clip.title,
@@ -80,9 +132,19 @@ fn validate_and_map_clip(
         end_time,
         sanitized_filename,
         is_selected: true,
+        speakers,
+        profile_id: None,
+        reframe: None,
     })
 }
 
+/// List the speakers named in a transcript's voice tags
+#[command]
+pub async fn get_transcript_speakers(transcript_path: String) -> Result<Vec<String>, String> {
+    let vtt_cues = TranscriptLoader::load(&transcript_path)?;
+    Ok(VttParser::get_speakers(&vtt_cues))
+}
+
 fn sanitize_filename(title: &str) -> String {
     title
         .chars()
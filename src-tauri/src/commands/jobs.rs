@@ -0,0 +1,11 @@
+use crate::services::JobRegistry;
+use tauri::{command, State};
+
+/// Stop a running clip generation or transcription job
+///
+/// The job's own command then returns the error `"cancelled"`. Returns false
+/// if the job had already finished.
+#[command]
+pub fn cancel_job(jobs: State<'_, JobRegistry>, job_id: String) -> bool {
+    jobs.cancel(&job_id)
+}
@@ -0,0 +1,9 @@
+use crate::models::MediaInfo;
+use crate::services::MediaInfoService;
+use tauri::command;
+
+/// Duration, resolution, codecs and audio tracks of a video file
+#[command]
+pub async fn probe_video(video_path: String) -> Result<MediaInfo, String> {
+    MediaInfoService::probe(&video_path).await
+}
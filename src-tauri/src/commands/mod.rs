@@ -2,8 +2,12 @@ pub mod settings;
 pub mod analyze;
 pub mod process;
 pub mod transcribe;
+pub mod jobs;
+pub mod media;
 
 pub use settings::*;
 pub use analyze::*;
 pub use process::*;
 pub use transcribe::*;
+pub use jobs::*;
+pub use media::*;
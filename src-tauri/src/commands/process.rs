@@ -1,39 +1,116 @@
-use crate::models::{ValidatedClip, ProcessingResult};
-use crate::services::FFmpegService;
-use tauri::{command, AppHandle, Manager};
+use crate::models::{HighlightReelOptions, HighlightReelResult, ValidatedClip, ProcessingResult};
+use crate::commands::settings::load_settings;
+use crate::services::{ClipSubtitles, FFmpegService, HighlightReelService, JobRegistry, TranscriptLoader};
+use tauri::{command, AppHandle, Manager, State};
 
+/// Encode the selected clips; `job_id` lets the frontend cancel the run
+///
+/// `profile_id` picks the export profile for clips that don't set their own.
+/// `burn_captions` overrides the captions setting. Burned-in captions and
+/// subtitle files come from the transcript at `transcript_path`.
 #[command]
+#[allow(clippy::too_many_arguments)]
 pub async fn generate_clips(
     app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
     video_path: String,
     clips: Vec<ValidatedClip>,
+    job_id: Option<String>,
+    profile_id: Option<String>,
+    transcript_path: Option<String>,
+    burn_captions: Option<bool>,
 ) -> Result<ProcessingResult, String> {
-    let clips_to_generate: Vec<ValidatedClip> = clips
-        .into_iter()
-        .filter(|c| c.is_selected)  // Only generate selected clips
-        .collect();
+    // Registered first, so the frontend can cancel it and every error goes through `finish`
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.start(&job_id);
     
-    if clips_to_generate.is_empty() {
-        return Err("No clips selected".to_string());
-    }
-    
-    let clip_count = clips_to_generate.len();
-    
-    // Progress tracking using Tauri events
-    let output_dir = FFmpegService::generate_clips(
-        video_path,
-        clips_to_generate,
-        |progress| {
-            let _ = app_handle.emit_all("clip-progress", progress);
-        },
-    ).await?;
+    let result = async {
+        let clips_to_generate: Vec<ValidatedClip> = clips
+            .into_iter()
+            .filter(|c| c.is_selected)  // Only generate selected clips
+            .collect();
+        
+        if clips_to_generate.is_empty() {
+            return Err("No clips selected".to_string());
+        }
+        
+        let settings = load_settings(&app_handle)?;
+        let burn_captions = burn_captions.unwrap_or(settings.captions.enabled);
+        let wants_subtitles = burn_captions || !settings.export.subtitle_files.is_empty();
+        let subtitles = match (wants_subtitles, transcript_path) {
+            (true, Some(transcript_path)) => Some(ClipSubtitles {
+                cues: TranscriptLoader::load(&transcript_path)?,
+                language: TranscriptLoader::load_language(&transcript_path),
+                burn_in: burn_captions.then(|| settings.captions.clone()),
+                files: settings.export.subtitle_files.clone(),
+            }),
+            (true, None) => {
+                println!("⚠️  Captions or subtitle files requested but no transcript was given, skipping them");
+                None
+            }
+            (false, _) => None,
+        };
+        
+        // Progress tracking using Tauri events
+        FFmpegService::generate_clips(
+            video_path,
+            clips_to_generate,
+            &settings.export,
+            profile_id.as_deref(),
+            subtitles.as_ref(),
+            &cancel,
+            move |progress| {
+                let _ = app_handle.emit_all("clip-progress", progress);
+            },
+        ).await
+    }.await;
+    let (output_dir, clips) = jobs.finish(&job_id, result)?;
     
     Ok(ProcessingResult {
         output_directory: output_dir,
-        clip_count,
+        clip_count: clips.iter().filter(|clip| clip.error.is_none()).count(),
+        clips,
     })
 }
 
+/// Render the selected clips, in the order given, into one highlight reel
+///
+/// Progress is reported through the same `clip-progress` events as `generate_clips`.
+#[command]
+pub async fn generate_highlight_reel(
+    app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
+    video_path: String,
+    clips: Vec<ValidatedClip>,
+    options: Option<HighlightReelOptions>,
+    job_id: Option<String>,
+) -> Result<HighlightReelResult, String> {
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.start(&job_id);
+    
+    let result = async {
+        let clips: Vec<ValidatedClip> = clips
+            .into_iter()
+            .filter(|c| c.is_selected)
+            .collect();
+        let options = options.unwrap_or_default();
+        
+        let settings = load_settings(&app_handle)?;
+        HighlightReelService::render(
+            &video_path,
+            &clips,
+            &options,
+            &settings.export,
+            &settings.captions,
+            &cancel,
+            move |progress| {
+                let _ = app_handle.emit_all("clip-progress", progress);
+            },
+        ).await
+    }.await;
+    jobs.finish(&job_id, result)
+}
+
 #[command]
 pub async fn open_in_file_explorer(path: String) -> Result<(), String> {
     #[cfg(target_os = "windows")]
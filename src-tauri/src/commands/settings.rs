@@ -1,3 +1,4 @@
+use crate::models::Settings;
 use tauri::command;
 use tauri::AppHandle;
 use std::fs;
@@ -8,15 +9,34 @@ fn get_settings_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
         .path_resolver()
         .app_data_dir()
         .ok_or("Failed to get app data directory")?;
-    
+
     fs::create_dir_all(&app_dir).map_err(|e| e.to_string())?;
     Ok(app_dir.join("settings.json"))
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Default)]
-struct Settings {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    openai_api_key: Option<String>,
+/// Read settings from disk, falling back to defaults if none are saved yet
+pub fn load_settings(app_handle: &AppHandle) -> Result<Settings, String> {
+    let settings_path = get_settings_path(app_handle)?;
+
+    if !settings_path.exists() {
+        return Ok(Settings::default());
+    }
+
+    let json = fs::read_to_string(settings_path)
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(&json)
+        .map_err(|e| e.to_string())
+}
+
+fn write_settings(app_handle: &AppHandle, settings: &Settings) -> Result<(), String> {
+    let settings_path = get_settings_path(app_handle)?;
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| e.to_string())?;
+
+    fs::write(settings_path, json)
+        .map_err(|e| e.to_string())
 }
 
 #[command]
@@ -24,38 +44,41 @@ pub async fn save_api_key(
     app_handle: AppHandle,
     api_key: String,
 ) -> Result<(), String> {
-    let settings_path = get_settings_path(&app_handle)?;
-    
-    let settings = Settings {
-        openai_api_key: Some(api_key),
-    };
-    
-    let json = serde_json::to_string_pretty(&settings)
-        .map_err(|e| e.to_string())?;
-    
-    fs::write(settings_path, json)
-        .map_err(|e| e.to_string())?;
-    
-    Ok(())
+    // Keep the rest of the settings intact
+    let mut settings = load_settings(&app_handle)?;
+    settings.openai_api_key = Some(api_key);
+
+    write_settings(&app_handle, &settings)
 }
 
 #[command]
 pub async fn get_api_key(
     app_handle: AppHandle,
 ) -> Result<Option<String>, String> {
-    let settings_path = get_settings_path(&app_handle)?;
-    
-    if !settings_path.exists() {
-        return Ok(None);
-    }
-    
-    let json = fs::read_to_string(settings_path)
-        .map_err(|e| e.to_string())?;
-    
-    let settings: Settings = serde_json::from_str(&json)
-        .map_err(|e| e.to_string())?;
-    
-    Ok(settings.openai_api_key)
+    Ok(load_settings(&app_handle)?.openai_api_key)
+}
+
+/// Get settings for the UI (the OpenAI key is only exposed through `get_api_key`)
+#[command]
+pub async fn get_settings(app_handle: AppHandle) -> Result<Settings, String> {
+    let mut settings = load_settings(&app_handle)?;
+    settings.openai_api_key = None;
+    Ok(settings)
+}
+
+/// Save settings from the UI, preserving the stored OpenAI key
+#[command]
+pub async fn save_settings(
+    app_handle: AppHandle,
+    settings: Settings,
+) -> Result<(), String> {
+    let existing = load_settings(&app_handle)?;
+    let settings = Settings {
+        openai_api_key: existing.openai_api_key,
+        ..settings
+    };
+
+    write_settings(&app_handle, &settings)
 }
 
 #[command]
@@ -68,6 +91,6 @@ pub async fn validate_api_key(api_key: String) -> Result<bool, String> {
         .send()
         .await
         .map_err(|e| e.to_string())?;
-    
+
     Ok(response.status().is_success())
 }
@@ -1,24 +1,67 @@
-use crate::services::WhisperService;
-use crate::commands::settings::get_api_key;
-use tauri::{command, AppHandle, Manager};
+use crate::models::{Glossary, TranscriptionOptions, TranscriptionResult};
+use crate::services::{backend_from_settings, HttpClient, JobRegistry, WhisperService};
+use crate::commands::settings::load_settings;
+use std::fs;
+use std::path::Path;
+use tauri::{command, AppHandle, Manager, State};
 
+/// Transcribe a video's audio; `job_id` lets the frontend cancel the run
 #[command]
 pub async fn generate_transcript_from_video(
     app_handle: AppHandle,
+    jobs: State<'_, JobRegistry>,
     video_path: String,
-) -> Result<String, String> {
-    // Get API key
-    let api_key = get_api_key(app_handle.clone()).await?
-        .ok_or("No API key configured. Please add your OpenAI API key in Settings.")?;
+    options: Option<TranscriptionOptions>,
+    job_id: Option<String>,
+) -> Result<TranscriptionResult, String> {
+    // Registered first, so the frontend can cancel it and every error goes through `finish`
+    let job_id = job_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = jobs.start(&job_id);
     
-    // Generate transcript with progress updates
-    let vtt_path = WhisperService::transcribe_video(
-        &api_key,
-        &video_path,
-        |message| {
-            let _ = app_handle.emit_all("transcription-progress", message);
-        },
-    ).await?;
+    let result = async {
+        let settings = load_settings(&app_handle)?;
+        
+        // Rate limits and dropped uploads are retried; tell the user while we wait
+        let retry_handle = app_handle.clone();
+        let http = HttpClient::new(&settings.network)?
+            .with_retry_listener(move |event| {
+                let _ = retry_handle.emit_all("transcription-progress", event.to_string());
+            })
+            .with_cancellation(cancel.clone());
+        
+        // OpenAI Whisper or the offline whisper.cpp sidecar
+        let backend = backend_from_settings(&settings.transcription, settings.openai_api_key.clone(), http)?;
+        
+        // Project glossary entries take precedence over the global ones
+        let glossary = Glossary::parse(&settings.transcription.glossary)
+            .merge(project_glossary(&video_path));
+        
+        // Generate transcript with progress updates
+        WhisperService::transcribe_video(
+            backend.as_ref(),
+            &video_path,
+            &options.unwrap_or_default(),
+            &glossary,
+            settings.transcription.apply_glossary_replacements,
+            &cancel,
+            &|message| {
+                let _ = app_handle.emit_all("transcription-progress", message);
+            },
+        ).await
+    }.await;
     
-    Ok(vtt_path)
+    jobs.finish(&job_id, result)
+}
+
+/// `glossary.txt` in the video's folder, if there is one
+fn project_glossary(video_path: &str) -> Glossary {
+    let glossary_path = Path::new(video_path).with_file_name("glossary.txt");
+    
+    match fs::read_to_string(&glossary_path) {
+        Ok(text) => {
+            println!("Using project glossary: {}", glossary_path.display());
+            Glossary::parse(&text)
+        }
+        Err(_) => Glossary::default(),
+    }
 }
@@ -6,17 +6,25 @@ mod models;
 mod services;
 
 use commands::*;
+use services::JobRegistry;
 
 fn main() {
     tauri::Builder::default()
+        .manage(JobRegistry::default())
         .invoke_handler(tauri::generate_handler![
             save_api_key,
             get_api_key,
+            get_settings,
+            save_settings,
             validate_api_key,
             analyze_transcript_for_clips,
+            get_transcript_speakers,
             generate_clips,
+            generate_highlight_reel,
             open_in_file_explorer,
             generate_transcript_from_video,
+            cancel_job,
+            probe_video,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
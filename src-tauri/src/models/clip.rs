@@ -1,11 +1,21 @@
+use super::{Reframe, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// OpenAI response structure
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ClipSuggestion {
     pub title: String,
-    pub start_time: String,  // HH:MM:SS or MM:SS
-    pub end_time: String,
+    pub start_time: Timestamp,  // Parsed from HH:MM:SS or MM:SS
+    pub end_time: Timestamp,
+    #[serde(default)]
+    pub score: Option<f32>,     // 1-10, used to rank candidates across windows
+}
+
+impl ClipSuggestion {
+    /// Unscored clips rank in the middle rather than first or last
+    pub fn score_or_default(&self) -> f32 {
+        self.score.unwrap_or(5.0)
+    }
 }
 
 /// Validated clip (ready for FFmpeg)
@@ -14,25 +24,47 @@ pub struct ValidatedClip {
     pub id: String,
     pub title: String,
     #[serde(rename = "startTime")]
-    pub start_time: String,       // FFmpeg-compatible format
+    pub start_time: Timestamp,    // Serialized as HH:MM:SS.mmm
     #[serde(rename = "endTime")]
-    pub end_time: String,
+    pub end_time: Timestamp,
     #[serde(rename = "sanitizedFilename")]
     pub sanitized_filename: String,
     #[serde(rename = "isSelected")]
     pub is_selected: bool,
+    #[serde(default)]
+    pub speakers: Vec<String>,  // Speakers heard within the clip
+    #[serde(rename = "profileId", default, skip_serializing_if = "Option::is_none")]
+    pub profile_id: Option<String>, // Export profile for this clip only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reframe: Option<Reframe>,   // Vertical/square output; None keeps the source frame
 }
 
 /// Processing result
 #[derive(Serialize)]
 pub struct ProcessingResult {
     pub output_directory: String,
-    pub clip_count: usize,
+    pub clip_count: usize,           // Clips generated successfully
+    pub clips: Vec<ClipResult>,      // Every selected clip, in order
+}
+
+/// How one clip in a batch turned out
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipResult {
+    pub id: String,
+    pub title: String,
+    pub output_path: Option<String>, // Set when the clip was written
+    pub error: Option<String>,       // Set when encoding failed
 }
 
 /// Progress event payload
 #[derive(Clone, Serialize)]
 pub struct ClipProgress {
-    pub current: usize,
+    pub current: usize,              // Clips finished so far
     pub total: usize,
+    pub active: usize,               // Clips being encoded right now
+    pub clip_index: usize,           // Clip this update is about (0-based)
+    pub clip_percent: f32,
+    pub overall_percent: f32,        // Weighted by clip duration
+    pub speed: Option<f32>,          // Encode speed of all workers combined, as a multiple of real time
+    pub eta_seconds: Option<u64>,
 }
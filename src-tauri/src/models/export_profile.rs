@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+
+/// Video encoder settings for an export profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoEncoding {
+    /// FFmpeg encoder, e.g. `libx264`, `libx265`, `libvpx-vp9`, `prores_ks`
+    pub codec: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crf: Option<u32>,
+    /// Target bitrate such as `8M` (`0` with a CRF means constant quality for VP9)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<String>,
+    /// Encoder profile, e.g. `0` (proxy) for ProRes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pixel_format: Option<String>,
+    /// Passed to FFmpeg as-is, after the options above
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+}
+
+/// Audio encoder settings for an export profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioEncoding {
+    /// FFmpeg encoder, e.g. `aac`, `libmp3lame`, `libopus`, `pcm_s16le`
+    pub codec: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channels: Option<u32>,
+}
+
+/// A named output format clips can be exported in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportProfile {
+    pub id: String,
+    pub name: String,
+    /// Output file extension, which also decides FFmpeg's container
+    pub extension: String,
+    /// None for audio-only profiles
+    #[serde(default)]
+    pub video: Option<VideoEncoding>,
+    /// None drops the audio
+    #[serde(default)]
+    pub audio: Option<AudioEncoding>,
+}
+
+impl ExportProfile {
+    /// Profile used when nothing else is chosen (the original clip format)
+    pub const DEFAULT_ID: &'static str = "h264";
+
+    /// Profiles every installation starts with
+    pub fn builtin() -> Vec<ExportProfile> {
+        let video = |codec: &str, preset: Option<&str>, crf: Option<u32>| VideoEncoding {
+            codec: codec.to_string(),
+            preset: preset.map(str::to_string),
+            crf,
+            bitrate: None,
+            profile: None,
+            pixel_format: None,
+            extra_args: Vec::new(),
+        };
+        let audio = |codec: &str, bitrate: Option<&str>| AudioEncoding {
+            codec: codec.to_string(),
+            bitrate: bitrate.map(str::to_string),
+            sample_rate: None,
+            channels: None,
+        };
+        let profile = |id: &str, name: &str, extension: &str, video, audio| ExportProfile {
+            id: id.to_string(),
+            name: name.to_string(),
+            extension: extension.to_string(),
+            video,
+            audio,
+        };
+
+        vec![
+            profile(
+                "h264",
+                "H.264 (MP4)",
+                "mp4",
+                Some(video("libx264", Some("fast"), Some(23))),
+                Some(audio("aac", Some("192k"))),
+            ),
+            profile(
+                "h264_hq",
+                "H.264 High Quality (MP4)",
+                "mp4",
+                Some(VideoEncoding {
+                    pixel_format: Some("yuv420p".to_string()),
+                    ..video("libx264", Some("slow"), Some(18))
+                }),
+                Some(audio("aac", Some("256k"))),
+            ),
+            profile(
+                "hevc",
+                "HEVC / H.265 (MP4)",
+                "mp4",
+                Some(VideoEncoding {
+                    // hvc1 is the tag QuickTime and iOS expect
+                    extra_args: vec!["-tag:v".to_string(), "hvc1".to_string()],
+                    ..video("libx265", Some("medium"), Some(24))
+                }),
+                Some(audio("aac", Some("192k"))),
+            ),
+            profile(
+                "vp9_webm",
+                "VP9 (WebM)",
+                "webm",
+                Some(VideoEncoding {
+                    bitrate: Some("0".to_string()),
+                    extra_args: vec!["-row-mt".to_string(), "1".to_string()],
+                    ..video("libvpx-vp9", None, Some(32))
+                }),
+                Some(audio("libopus", Some("128k"))),
+            ),
+            profile(
+                "prores_proxy",
+                "ProRes 422 Proxy (MOV)",
+                "mov",
+                Some(VideoEncoding {
+                    profile: Some("0".to_string()),
+                    pixel_format: Some("yuv422p10le".to_string()),
+                    ..video("prores_ks", None, None)
+                }),
+                Some(audio("pcm_s16le", None)),
+            ),
+            profile("mp3", "Audio only (MP3)", "mp3", None, Some(audio("libmp3lame", Some("192k")))),
+            profile("m4a", "Audio only (M4A)", "m4a", None, Some(audio("aac", Some("192k")))),
+            profile("wav", "Audio only (WAV)", "wav", None, Some(audio("pcm_s16le", None))),
+        ]
+    }
+
+    pub fn is_audio_only(&self) -> bool {
+        self.video.is_none()
+    }
+
+    /// MP4-family containers, which want the index up front for streaming
+    pub fn is_mp4_family(&self) -> bool {
+        matches!(self.extension.as_str(), "mp4" | "m4a" | "m4v" | "mov")
+    }
+
+    /// Codec the profile's video encoder produces, as ffprobe names it
+    pub fn video_codec(&self) -> Option<&str> {
+        let encoder = self.video.as_ref()?.codec.as_str();
+        Some(match encoder {
+            "libx264" | "h264_nvenc" | "h264_qsv" | "h264_amf" | "h264_videotoolbox" => "h264",
+            "libx265" | "hevc_nvenc" | "hevc_qsv" | "hevc_amf" | "hevc_videotoolbox" => "hevc",
+            "libvpx" => "vp8",
+            "libvpx-vp9" | "vp9_qsv" => "vp9",
+            "libaom-av1" | "libsvtav1" | "librav1e" | "av1_nvenc" | "av1_qsv" => "av1",
+            "prores_ks" | "prores_aw" | "prores_videotoolbox" => "prores",
+            "libxvid" => "mpeg4",
+            // Native FFmpeg encoders share the codec's name (mpeg4, prores, ...)
+            other => other,
+        })
+    }
+
+    /// Whether this profile's container can hold a stream-copied video codec
+    pub fn can_hold_video(&self, codec: &str) -> bool {
+        match self.extension.as_str() {
+            "mp4" | "m4v" => matches!(codec, "h264" | "hevc" | "av1" | "mpeg4"),
+            "mov" => matches!(codec, "h264" | "hevc" | "prores" | "mpeg4"),
+            "webm" => matches!(codec, "vp8" | "vp9" | "av1"),
+            "mkv" => true,
+            _ => false,
+        }
+    }
+
+    /// Whether this profile's container can hold a stream-copied audio codec
+    pub fn can_hold_audio(&self, codec: &str) -> bool {
+        match self.extension.as_str() {
+            "mp4" | "m4v" | "m4a" => matches!(codec, "aac" | "mp3" | "ac3" | "eac3" | "alac"),
+            "mov" => matches!(codec, "aac" | "mp3" | "alac") || codec.starts_with("pcm_"),
+            "webm" => matches!(codec, "opus" | "vorbis"),
+            "mkv" => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_the_codec_each_builtin_profile_encodes() {
+        let codecs: Vec<(String, Option<String>)> = ExportProfile::builtin()
+            .iter()
+            .map(|profile| (profile.id.clone(), profile.video_codec().map(str::to_string)))
+            .collect();
+        let expected = [
+            ("h264", Some("h264")),
+            ("h264_hq", Some("h264")),
+            ("hevc", Some("hevc")),
+            ("vp9_webm", Some("vp9")),
+            ("prores_proxy", Some("prores")),
+            ("mp3", None),
+            ("m4a", None),
+            ("wav", None),
+        ];
+
+        assert_eq!(codecs.len(), expected.len());
+        for ((id, codec), (expected_id, expected_codec)) in codecs.iter().zip(expected) {
+            assert_eq!(id, expected_id);
+            assert_eq!(codec.as_deref(), expected_codec, "profile {}", id);
+        }
+    }
+}
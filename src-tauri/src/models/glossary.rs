@@ -0,0 +1,230 @@
+use crate::models::WordTiming;
+use regex::{NoExpand, Regex, RegexBuilder};
+
+/// Known spellings for names, products and acronyms
+///
+/// Written one entry per line:
+///
+/// ```text
+/// # comments and blank lines are ignored
+/// ClipScribe
+/// clip scribe, clipscribe => ClipScribe
+/// ```
+///
+/// Every correct spelling becomes a transcription hint; `=>` lines also
+/// replace the listed misspellings after transcription.
+#[derive(Debug, Clone, Default)]
+pub struct Glossary {
+    pub terms: Vec<String>,
+    pub replacements: Vec<Replacement>,
+}
+
+/// A misspelling and the spelling that replaces it
+#[derive(Debug, Clone)]
+pub struct Replacement {
+    pub find: String,
+    pub replace: String,
+}
+
+// Whisper only reads the last 224 tokens of its prompt
+const MAX_PROMPT_CHARS: usize = 800;
+
+impl Glossary {
+    pub fn parse(text: &str) -> Self {
+        let mut glossary = Glossary::default();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let term = match line.split_once("=>") {
+                Some((finds, replace)) => {
+                    let replace = replace.trim();
+                    for find in finds.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                        glossary.replacements.push(Replacement {
+                            find: find.to_string(),
+                            replace: replace.to_string(),
+                        });
+                    }
+                    replace
+                }
+                None => line,
+            };
+
+            if !term.is_empty() && !glossary.terms.iter().any(|t| t == term) {
+                glossary.terms.push(term.to_string());
+            }
+        }
+
+        glossary
+    }
+
+    /// Combine with another glossary; entries in `other` are applied first
+    pub fn merge(self, other: Glossary) -> Self {
+        let mut terms = other.terms;
+        for term in self.terms {
+            if !terms.contains(&term) {
+                terms.push(term);
+            }
+        }
+
+        let mut replacements = other.replacements;
+        replacements.extend(self.replacements);
+
+        Glossary { terms, replacements }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.replacements.is_empty()
+    }
+
+    /// Vocabulary hint for Whisper's `prompt` parameter
+    pub fn prompt(&self) -> Option<String> {
+        let mut prompt = String::from("Glossary:");
+        for term in &self.terms {
+            if prompt.len() + term.len() + 2 > MAX_PROMPT_CHARS {
+                break;
+            }
+            prompt.push(' ');
+            prompt.push_str(term);
+            prompt.push(',');
+        }
+
+        if prompt.ends_with(',') {
+            prompt.pop();
+            prompt.push('.');
+            Some(prompt)
+        } else {
+            None
+        }
+    }
+
+    /// Case-insensitive whole-word find/replace of every misspelling
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for replacement in &self.replacements {
+            if let Some(regex) = replacement.regex() {
+                result = regex.replace_all(&result, NoExpand(&replacement.replace)).into_owned();
+            }
+        }
+        result
+    }
+
+    /// `apply` for word timings, so words stay in step with the corrected text
+    ///
+    /// A misspelling spread over several words ("clip scribe") becomes one
+    /// word timed from the first word's start to the last word's end.
+    pub fn apply_words(&self, words: &[WordTiming]) -> Vec<WordTiming> {
+        let mut words = words.to_vec();
+        for replacement in &self.replacements {
+            let Some(regex) = replacement.regex() else {
+                continue;
+            };
+            let count = replacement.find.split_whitespace().count().max(1);
+
+            let mut index = 0;
+            while index + count <= words.len() {
+                let parts: Vec<&str> = words[index..index + count].iter().map(|word| word.word.trim()).collect();
+                let joined = parts.join(" ");
+
+                // The match has to reach into the first and the last of the words
+                let first_end = parts[0].len();
+                let last_start = joined.len() - parts[count - 1].len();
+                if regex.find_iter(&joined).any(|m| m.start() < first_end && m.end() > last_start) {
+                    let merged = WordTiming {
+                        word: regex.replace_all(&joined, NoExpand(&replacement.replace)).into_owned(),
+                        start: words[index].start,
+                        end: words[index + count - 1].end,
+                    };
+                    words.splice(index..index + count, [merged]);
+                }
+                index += 1;
+            }
+        }
+        words
+    }
+}
+
+impl Replacement {
+    fn regex(&self) -> Option<Regex> {
+        // \b only works next to word characters ("C++" has none at its end)
+        let boundary = |c: Option<char>| match c {
+            Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+            _ => "",
+        };
+
+        let pattern = format!(
+            "{}{}{}",
+            boundary(self.find.chars().next()),
+            regex::escape(&self.find),
+            boundary(self.find.chars().last())
+        );
+
+        RegexBuilder::new(&pattern).case_insensitive(true).build().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Timestamp;
+
+    fn words(timed: &[(&str, u64, u64)]) -> Vec<WordTiming> {
+        timed
+            .iter()
+            .map(|&(word, start, end)| WordTiming {
+                word: word.to_string(),
+                start: Timestamp::from_millis(start),
+                end: Timestamp::from_millis(end),
+            })
+            .collect()
+    }
+
+    fn texts(words: &[WordTiming]) -> Vec<&str> {
+        words.iter().map(|word| word.word.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_terms_and_replacements() {
+        let glossary = Glossary::parse("# names\nFFmpeg\n\nclip scribe, clipscribe => ClipScribe\n");
+
+        assert_eq!(glossary.terms, vec!["FFmpeg".to_string(), "ClipScribe".to_string()]);
+        assert_eq!(glossary.replacements.len(), 2);
+        assert_eq!(glossary.prompt().as_deref(), Some("Glossary: FFmpeg, ClipScribe."));
+    }
+
+    #[test]
+    fn replaces_whole_words_in_text() {
+        let glossary = Glossary::parse("clip scribe, clipscribe => ClipScribe");
+
+        assert_eq!(glossary.apply("I use Clip Scribe and clipscribe."), "I use ClipScribe and ClipScribe.");
+        assert_eq!(glossary.apply("clipscribes"), "clipscribes");
+    }
+
+    #[test]
+    fn replaces_single_words_keeping_their_timing() {
+        let glossary = Glossary::parse("clipscribe => ClipScribe");
+        let corrected = glossary.apply_words(&words(&[("try", 0, 200), ("clipscribe,", 200, 800)]));
+
+        assert_eq!(texts(&corrected), vec!["try", "ClipScribe,"]);
+        assert_eq!(corrected[1].start, Timestamp::from_millis(200));
+        assert_eq!(corrected[1].end, Timestamp::from_millis(800));
+    }
+
+    #[test]
+    fn merges_multi_word_matches_into_one_word() {
+        let glossary = Glossary::parse("clip scribe => ClipScribe");
+        let corrected = glossary.apply_words(&words(&[
+            ("I", 0, 100),
+            ("love", 100, 400),
+            ("Clip", 400, 700),
+            ("scribe.", 700, 1100),
+            ("clip", 1500, 1700),
+        ]));
+
+        assert_eq!(texts(&corrected), vec!["I", "love", "ClipScribe.", "clip"]);
+        assert_eq!(corrected[2].start, Timestamp::from_millis(400));
+        assert_eq!(corrected[2].end, Timestamp::from_millis(1100));
+    }
+}
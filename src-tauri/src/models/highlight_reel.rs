@@ -0,0 +1,87 @@
+use super::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// How one segment of a highlight reel leads into the next
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReelTransition {
+    /// Straight cut
+    #[default]
+    Cut,
+    /// Blend the end of one segment into the start of the next
+    Crossfade,
+    /// Fade out to black, then in from black
+    DipToBlack,
+}
+
+/// Title cards are held for at least this long, so the title can be read
+const MIN_TITLE_CARD_SECONDS: f64 = 0.5;
+
+/// Title cards longer than this are most likely a typo
+const MAX_TITLE_CARD_SECONDS: f64 = 10.0;
+
+/// Options for rendering clips into one compilation video
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighlightReelOptions {
+    pub transition: ReelTransition,
+    /// Length of each transition (shortened for very short segments)
+    pub transition_seconds: f64,
+    /// Show each clip's title on a black card before it
+    pub title_cards: bool,
+    pub title_card_seconds: f64,
+    /// Blend the audio across transitions; otherwise it fades out and back in
+    pub audio_crossfade: bool,
+    /// Export profile (falls back to the default profile)
+    pub profile_id: Option<String>,
+}
+
+impl Default for HighlightReelOptions {
+    fn default() -> Self {
+        Self {
+            transition: ReelTransition::Cut,
+            transition_seconds: 0.75,
+            title_cards: false,
+            title_card_seconds: 2.0,
+            audio_crossfade: true,
+            profile_id: None,
+        }
+    }
+}
+
+impl HighlightReelOptions {
+    /// `title_card_seconds`, kept within half a second and ten seconds
+    pub fn title_card_seconds(&self) -> f64 {
+        // `clamp` keeps NaN, which would panic in `Duration::from_secs_f64`
+        if self.title_card_seconds.is_nan() {
+            MIN_TITLE_CARD_SECONDS
+        } else {
+            self.title_card_seconds.clamp(MIN_TITLE_CARD_SECONDS, MAX_TITLE_CARD_SECONDS)
+        }
+    }
+}
+
+/// Where the highlight reel was written
+#[derive(Debug, Clone, Serialize)]
+pub struct HighlightReelResult {
+    pub output_path: String,
+    pub output_directory: String,
+    pub duration: Timestamp,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_title_cards_readable_and_short() {
+        let seconds = |title_card_seconds| HighlightReelOptions { title_card_seconds, ..Default::default() }.title_card_seconds();
+
+        assert_eq!(seconds(2.0), 2.0);
+        assert_eq!(seconds(0.1), 0.5);
+        assert_eq!(seconds(-3.0), 0.5);
+        assert_eq!(seconds(1e300), 10.0);
+        assert_eq!(seconds(f64::INFINITY), 10.0);
+        assert_eq!(seconds(f64::NAN), 0.5);
+    }
+}
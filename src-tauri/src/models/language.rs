@@ -0,0 +1,53 @@
+/// Languages offered for transcription: ISO 639-1 code and English name
+///
+/// Whisper understands many more; anything not listed here is passed
+/// through as-is.
+pub const LANGUAGES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("de", "German"),
+    ("fr", "French"),
+    ("it", "Italian"),
+    ("pt", "Portuguese"),
+    ("nl", "Dutch"),
+    ("sv", "Swedish"),
+    ("da", "Danish"),
+    ("no", "Norwegian"),
+    ("fi", "Finnish"),
+    ("pl", "Polish"),
+    ("cs", "Czech"),
+    ("ru", "Russian"),
+    ("uk", "Ukrainian"),
+    ("tr", "Turkish"),
+    ("el", "Greek"),
+    ("ar", "Arabic"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("zh", "Chinese"),
+    ("id", "Indonesian"),
+    ("vi", "Vietnamese"),
+    ("th", "Thai"),
+];
+
+/// Normalize a language code or name ("de", "DE", "german") to its code
+///
+/// Whisper's verbose JSON reports names while its parameters take codes.
+pub fn normalize_language(value: &str) -> String {
+    let value = value.trim();
+    LANGUAGES
+        .iter()
+        .find(|(code, name)| code.eq_ignore_ascii_case(value) || name.eq_ignore_ascii_case(value))
+        .map(|(code, _)| code.to_string())
+        .unwrap_or_else(|| value.to_lowercase())
+}
+
+/// English name for a language code, for prompts and the UI
+pub fn language_name(code: &str) -> String {
+    LANGUAGES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code))
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| code.to_string())
+}
@@ -0,0 +1,52 @@
+use super::Timestamp;
+use serde::Serialize;
+
+/// What ffprobe reports about a media file
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaInfo {
+    pub path: String,
+    pub duration: Timestamp,
+    /// Container timestamp of the first frame (usually 0, but not for MPEG-TS)
+    pub start_time: f64,
+    /// Container as ffprobe names it, e.g. `mov,mp4,m4a,3gp,3g2,mj2`
+    pub format_name: String,
+    pub size_bytes: Option<u64>,
+    pub bit_rate: Option<u64>,
+    /// First video stream (cover art doesn't count)
+    pub video: Option<VideoStreamInfo>,
+    pub audio_tracks: Vec<AudioStreamInfo>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoStreamInfo {
+    pub codec: String,               // e.g. "h264", "hevc", "prores"
+    pub profile: Option<String>,     // e.g. "High", "422 HQ"
+    pub width: u32,                  // Display size, with any rotation applied
+    pub height: u32,
+    pub frame_rate: Option<f64>,     // Average frames per second
+    pub pixel_format: Option<String>,
+    pub bit_rate: Option<u64>,
+    pub level: Option<i64>,          // As ffprobe reports it, e.g. 41 for H.264 level 4.1
+    pub time_base: Option<String>,   // e.g. "1/15360"
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioStreamInfo {
+    pub index: u32,                  // Stream index within the file
+    pub codec: String,               // e.g. "aac", "pcm_s16le"
+    pub channels: u32,
+    pub channel_layout: Option<String>, // e.g. "stereo", "5.1(side)"
+    pub sample_rate: Option<u32>,
+    pub language: Option<String>,
+}
+
+impl MediaInfo {
+    pub fn video_codec(&self) -> Option<&str> {
+        self.video.as_ref().map(|video| video.codec.as_str())
+    }
+
+    /// Codec of the first audio track, which is the one clips keep
+    pub fn audio_codec(&self) -> Option<&str> {
+        self.audio_tracks.first().map(|audio| audio.codec.as_str())
+    }
+}
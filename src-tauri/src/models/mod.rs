@@ -1,5 +1,23 @@
 pub mod clip;
+pub mod export_profile;
+pub mod glossary;
+pub mod highlight_reel;
+pub mod language;
+pub mod media;
+pub mod reframe;
+pub mod settings;
+pub mod timestamp;
+pub mod transcription;
 pub mod vtt;
 
 pub use clip::*;
+pub use export_profile::*;
+pub use glossary::*;
+pub use highlight_reel::*;
+pub use language::*;
+pub use media::*;
+pub use reframe::*;
+pub use settings::*;
+pub use timestamp::*;
+pub use transcription::*;
 pub use vtt::*;
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Frame shape for social platforms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AspectRatio {
+    /// Shorts, Reels, TikTok
+    #[serde(rename = "9:16")]
+    Vertical,
+    #[serde(rename = "1:1")]
+    Square,
+}
+
+impl AspectRatio {
+    /// Width and height as a ratio, e.g. (9, 16)
+    pub fn ratio(self) -> (u32, u32) {
+        match self {
+            AspectRatio::Vertical => (9, 16),
+            AspectRatio::Square => (1, 1),
+        }
+    }
+
+    /// Output resolution (what the platforms recommend uploading)
+    pub fn output_size(self) -> (u32, u32) {
+        match self {
+            AspectRatio::Vertical => (1080, 1920),
+            AspectRatio::Square => (1080, 1080),
+        }
+    }
+}
+
+/// How the source frame is fitted into the new shape
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ReframeFit {
+    /// Crop the middle of the frame
+    Center,
+    /// Crop at a horizontal position: 0.0 = left edge, 0.5 = center, 1.0 = right edge
+    Offset { position: f64 },
+    /// Crop a rectangle of the source (in source pixels), scaled to fill the frame
+    Crop { x: u32, y: u32, width: u32, height: u32 },
+    /// Show the whole frame over a blurred, zoomed-in copy of itself
+    BlurredBackground,
+}
+
+/// Reframe a clip for vertical or square output
+///
+/// Serialized flat, e.g. `{ "aspect": "9:16", "mode": "offset", "position": 0.3 }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reframe {
+    pub aspect: AspectRatio,
+    #[serde(flatten)]
+    pub fit: ReframeFit,
+}
+
+impl Reframe {
+    /// Check a crop rectangle against the source's frame size (as displayed,
+    /// with any rotation applied), when that's known
+    pub fn validate(&self, source_size: Option<(u32, u32)>) -> Result<(), String> {
+        if let ReframeFit::Crop { x, y, width, height } = self.fit {
+            if width == 0 || height == 0 {
+                return Err(format!("Crop rectangle {}x{} is empty", width, height));
+            }
+            if let Some((source_width, source_height)) = source_size {
+                let right = x as u64 + width as u64;
+                let bottom = y as u64 + height as u64;
+                if right > source_width as u64 || bottom > source_height as u64 {
+                    return Err(format!(
+                        "Crop rectangle {}x{} at {},{} doesn't fit in the {}x{} video",
+                        width, height, x, y, source_width, source_height
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crop(x: u32, y: u32, width: u32, height: u32) -> Reframe {
+        Reframe { aspect: AspectRatio::Vertical, fit: ReframeFit::Crop { x, y, width, height } }
+    }
+
+    #[test]
+    fn validates_crop_rectangles() {
+        let source = Some((1920, 1080));
+
+        assert!(crop(656, 0, 608, 1080).validate(source).is_ok());
+        assert!(crop(0, 0, 1920, 1080).validate(source).is_ok());
+        assert!(crop(1400, 0, 608, 1080).validate(source).is_err());
+        assert!(crop(656, 1, 608, 1080).validate(source).is_err());
+        assert!(crop(u32::MAX, 0, 608, 1080).validate(source).is_err());
+
+        // Empty rectangles are caught even when the source wasn't probed
+        assert!(crop(0, 0, 0, 1080).validate(source).is_err());
+        assert!(crop(0, 0, 608, 0).validate(None).is_err());
+        assert!(crop(1400, 0, 608, 1080).validate(None).is_ok());
+    }
+
+    #[test]
+    fn only_checks_crops() {
+        let center = Reframe { aspect: AspectRatio::Square, fit: ReframeFit::Center };
+        assert!(center.validate(Some((640, 360))).is_ok());
+    }
+}
@@ -0,0 +1,319 @@
+use super::ExportProfile;
+use serde::{Deserialize, Serialize};
+
+/// Persisted application settings (settings.json in the app data directory)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openai_api_key: Option<String>,
+    #[serde(default)]
+    pub analysis: AnalysisSettings,
+    #[serde(default)]
+    pub network: NetworkSettings,
+    #[serde(default)]
+    pub transcription: TranscriptionSettings,
+    #[serde(default)]
+    pub export: ExportSettings,
+    #[serde(default)]
+    pub captions: CaptionSettings,
+}
+
+/// Which API shape the clip analysis provider speaks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmProviderKind {
+    /// OpenAI Responses API (`/responses`)
+    #[default]
+    Responses,
+    /// OpenAI Chat Completions API (`/chat/completions`)
+    ChatCompletions,
+    /// Any server exposing an OpenAI-compatible `/chat/completions` endpoint
+    /// (Ollama, llama.cpp server, vLLM...), usually running locally
+    OpenaiCompatible,
+}
+
+/// LLM settings for transcript analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisSettings {
+    pub provider: LlmProviderKind,
+    /// API root including the version segment, e.g. `http://localhost:11434/v1`.
+    /// Falls back to the provider's default when empty.
+    pub base_url: Option<String>,
+    pub model: String,
+    pub reasoning_effort: String, // "minimal", "low", "medium", "high"
+    pub verbosity: String,        // "low", "medium", "high"
+    /// Key for OpenAI-compatible servers that require one (OpenAI providers use `openai_api_key`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Transcript length sent per request; longer transcripts are analyzed in windows (0 = never split)
+    pub window_minutes: u32,
+    /// Transcript time shared by consecutive windows
+    pub window_overlap_seconds: u32,
+    /// Upper bound on the number of clips returned for the whole video
+    pub max_clips: usize,
+    /// Send a JSON schema for the response (OpenAI providers always do; some local servers can't)
+    pub structured_output: bool,
+    /// Move clip edges to the nearest sentence start and end
+    pub snap_to_sentences: bool,
+    /// Furthest a clip edge is moved to reach a sentence boundary
+    pub sentence_snap_seconds: u32,
+    /// Added before each suggested clip so the first syllable isn't cut off
+    pub pre_roll_ms: u64,
+    /// Added after each suggested clip so it doesn't end abruptly
+    pub post_roll_ms: u64,
+}
+
+impl Default for AnalysisSettings {
+    fn default() -> Self {
+        Self {
+            provider: LlmProviderKind::Responses,
+            base_url: None,
+            model: "gpt-5-mini".to_string(),
+            reasoning_effort: "minimal".to_string(), // Fast analysis, simple task
+            verbosity: "low".to_string(),            // Concise JSON output only
+            api_key: None,
+            window_minutes: 30,
+            window_overlap_seconds: 60,
+            max_clips: 8,
+            structured_output: true,
+            snap_to_sentences: true,
+            sentence_snap_seconds: 8,
+            pre_roll_ms: 250,
+            post_roll_ms: 500,
+        }
+    }
+}
+
+impl AnalysisSettings {
+    /// Base URL with the provider default applied and no trailing slash
+    pub fn resolved_base_url(&self) -> String {
+        let default = match self.provider {
+            LlmProviderKind::Responses | LlmProviderKind::ChatCompletions => "https://api.openai.com/v1",
+            LlmProviderKind::OpenaiCompatible => "http://localhost:11434/v1",
+        };
+
+        self.base_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    }
+}
+
+/// Which engine turns audio into a transcript
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionBackendKind {
+    /// OpenAI's hosted `whisper-1` model
+    #[default]
+    OpenaiWhisper,
+    /// Bundled whisper.cpp sidecar running on the CPU, fully offline
+    WhisperCpp,
+}
+
+/// Speech-to-text settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptionSettings {
+    pub backend: TranscriptionBackendKind,
+    /// GGML model file for whisper.cpp (e.g. `ggml-base.en.bin`)
+    pub whisper_cpp_model_path: Option<String>,
+    /// CPU threads for whisper.cpp (0 = one per core)
+    pub whisper_cpp_threads: u32,
+    /// Audio longer than this is split into chunks (cut on silence where possible)
+    pub chunk_minutes: u32,
+    /// Audio shared by neighbouring chunks so words on a cut aren't lost
+    pub chunk_overlap_seconds: u32,
+    /// Chunks transcribed at the same time
+    pub max_concurrent_chunks: usize,
+    /// Global glossary (see `Glossary::parse`); a `glossary.txt` beside the
+    /// video adds project-specific entries
+    pub glossary: String,
+    /// Replace known misspellings in the transcript after transcription
+    pub apply_glossary_replacements: bool,
+}
+
+impl Default for TranscriptionSettings {
+    fn default() -> Self {
+        Self {
+            backend: TranscriptionBackendKind::OpenaiWhisper,
+            whisper_cpp_model_path: None,
+            whisper_cpp_threads: 0,
+            chunk_minutes: 10,
+            chunk_overlap_seconds: 5,
+            max_concurrent_chunks: 3,
+            glossary: String::new(),
+            apply_glossary_replacements: true,
+        }
+    }
+}
+
+/// How clips are cut out of the source video
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportMode {
+    /// Re-encode every clip with its export profile (works with any source)
+    #[default]
+    Reencode,
+    /// Copy the source streams untouched when the clip starts on a keyframe,
+    /// otherwise re-encode the clip
+    StreamCopy,
+    /// Copy the source streams, re-encoding only the frames between the
+    /// start and the next keyframe when the start isn't on one
+    SmartRender,
+}
+
+/// Subtitle file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitleFormat {
+    Vtt,
+    Srt,
+}
+
+impl SubtitleFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SubtitleFormat::Vtt => "vtt",
+            SubtitleFormat::Srt => "srt",
+        }
+    }
+}
+
+/// Clip encoding settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportSettings {
+    pub mode: ExportMode,
+    /// Profile for clips and jobs that don't pick one
+    pub default_profile: String,
+    pub profiles: Vec<ExportProfile>,
+    /// Clips encoded at the same time
+    pub max_concurrent_clips: usize,
+    /// `-threads` for each FFmpeg process (0 = let FFmpeg decide)
+    pub threads_per_clip: u32,
+    /// Subtitle files written beside each clip, holding the lines spoken in it
+    pub subtitle_files: Vec<SubtitleFormat>,
+    /// Extra source exported before and after each clip for trimming in an
+    /// editor, with the real in and out points in the file's comment (0 = none)
+    pub handle_seconds: u32,
+    /// Fade the picture and sound in at the start of each clip (0 = none)
+    pub fade_in_ms: u64,
+    /// Fade the picture and sound out at the end of each clip (0 = none)
+    pub fade_out_ms: u64,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            mode: ExportMode::Reencode,
+            default_profile: ExportProfile::DEFAULT_ID.to_string(),
+            profiles: ExportProfile::builtin(),
+            max_concurrent_clips: 2,
+            threads_per_clip: 0,
+            subtitle_files: Vec::new(),
+            handle_seconds: 0,
+            fade_in_ms: 0,
+            fade_out_ms: 0,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Look up a profile by ID, falling back to the default profile
+    pub fn profile(&self, id: Option<&str>) -> ExportProfile {
+        let find = |id: &str| self.profiles.iter().find(|profile| profile.id == id);
+
+        if let Some(id) = id {
+            if let Some(profile) = find(id) {
+                return profile.clone();
+            }
+            println!("⚠️  Unknown export profile '{}', using the default", id);
+        }
+
+        find(&self.default_profile)
+            .or_else(|| self.profiles.first())
+            .cloned()
+            .unwrap_or_else(|| ExportProfile::builtin().remove(0))
+    }
+}
+
+/// Where burned-in captions sit in the frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptionPosition {
+    #[default]
+    Bottom,
+    Middle,
+    Top,
+}
+
+/// Look of captions burned into clips
+///
+/// Sizes are measured against a frame 1080 pixels tall and scale with the
+/// actual output, so the same settings suit landscape and vertical clips.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionSettings {
+    /// Burn captions into clips unless a job says otherwise
+    pub enabled: bool,
+    pub font: String,
+    pub font_size: u32,
+    pub bold: bool,
+    /// Colours as `#RRGGBB`
+    pub colour: String,
+    pub highlight_colour: String,
+    pub outline_colour: String,
+    pub outline_width: f32,
+    pub position: CaptionPosition,
+    /// Distance from the top or bottom edge
+    pub margin: u32,
+    /// Highlight each word as it's spoken (needs word timings in the transcript)
+    pub word_highlight: bool,
+}
+
+impl Default for CaptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            font: "Arial".to_string(),
+            font_size: 64,
+            bold: true,
+            colour: "#FFFFFF".to_string(),
+            highlight_colour: "#FFD400".to_string(),
+            outline_colour: "#000000".to_string(),
+            outline_width: 4.0,
+            position: CaptionPosition::Bottom,
+            margin: 120,
+            word_highlight: true,
+        }
+    }
+}
+
+/// Timeouts and retry policy for API calls (OpenAI and compatible servers)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub connect_timeout_seconds: u64,
+    /// Whole-request limit, including uploading audio and waiting for the model
+    pub request_timeout_seconds: u64,
+    /// Retries after the first attempt for 429s, 5xx and dropped connections
+    pub max_retries: u32,
+    pub retry_base_delay_ms: u64,
+    pub retry_max_delay_seconds: u64,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout_seconds: 30,
+            request_timeout_seconds: 600,
+            max_retries: 4,
+            retry_base_delay_ms: 1000,
+            retry_max_delay_seconds: 60,
+        }
+    }
+}
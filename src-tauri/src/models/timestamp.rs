@@ -0,0 +1,211 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Position in a media timeline with millisecond precision
+///
+/// Parses `HH:MM:SS.mmm`, `MM:SS.mmm`, `HH:MM:SS`, SRT-style `HH:MM:SS,mmm`
+/// and plain seconds (`74.5`). Always formats as `HH:MM:SS.mmm`, which both
+/// WebVTT and FFmpeg accept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    millis: u64,
+}
+
+impl Timestamp {
+    pub fn from_millis(millis: u64) -> Self {
+        Self { millis }
+    }
+
+    /// From fractional seconds as printed by FFmpeg (negative values clamp to zero)
+    pub fn from_secs_f64(seconds: f64) -> Self {
+        Self::from_millis((seconds.max(0.0) * 1000.0).round() as u64)
+    }
+
+    /// Time since the start of the media
+    pub fn as_duration(self) -> Duration {
+        Duration::from_millis(self.millis)
+    }
+
+    /// Distance between two timestamps regardless of order
+    pub fn abs_diff(self, other: Timestamp) -> Duration {
+        Duration::from_millis(self.millis.abs_diff(other.millis))
+    }
+
+    /// Format for FFmpeg `-ss`/`-to` arguments, keeping milliseconds
+    pub fn to_ffmpeg(self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hours = self.millis / 3_600_000;
+        let minutes = (self.millis / 60_000) % 60;
+        let seconds = (self.millis / 1000) % 60;
+        let millis = self.millis % 1000;
+
+        write!(f, "{:02}:{:02}:{:02}.{:03}", hours, minutes, seconds, millis)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid timestamp: '{}'", s);
+        let trimmed = s.trim();
+
+        let (clock, fraction) = match trimmed.split_once(['.', ',']) {
+            Some((clock, fraction)) => (clock, fraction),
+            None => (trimmed, ""),
+        };
+
+        // Fractional part is a decimal fraction: ".5" is 500ms, ".1234" truncates to 123ms
+        if !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let millis: u64 = format!("{:0<3}", fraction)[..3].parse().map_err(|_| invalid())?;
+
+        let parts = clock
+            .split(':')
+            .map(|part| {
+                if part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                part.parse::<u64>().ok()
+            })
+            .collect::<Option<Vec<u64>>>()
+            .ok_or_else(invalid)?;
+
+        let (hours, minutes, seconds) = match parts.as_slice() {
+            [s] => (0, 0, *s),
+            [m, s] if *s < 60 => (0, *m, *s),
+            [h, m, s] if *m < 60 && *s < 60 => (*h, *m, *s),
+            _ => return Err(invalid()),
+        };
+
+        // Hours are unbounded, so a long enough one would overflow
+        let millis = hours
+            .checked_mul(60)
+            .and_then(|total| total.checked_add(minutes))
+            .and_then(|total| total.checked_mul(60))
+            .and_then(|total| total.checked_add(seconds))
+            .and_then(|total| total.checked_mul(1000))
+            .and_then(|total| total.checked_add(millis))
+            .ok_or_else(invalid)?;
+
+        Ok(Self { millis })
+    }
+}
+
+/// Adding a duration saturates rather than overflowing
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, rhs: Duration) -> Timestamp {
+        let rhs = u64::try_from(rhs.as_millis()).unwrap_or(u64::MAX);
+        Timestamp::from_millis(self.millis.saturating_add(rhs))
+    }
+}
+
+/// Subtracting a duration clamps at zero rather than underflowing
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, rhs: Duration) -> Timestamp {
+        let rhs = u64::try_from(rhs.as_millis()).unwrap_or(u64::MAX);
+        Timestamp::from_millis(self.millis.saturating_sub(rhs))
+    }
+}
+
+/// Elapsed time between two timestamps (zero if `rhs` is later)
+impl Sub<Timestamp> for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, rhs: Timestamp) -> Duration {
+        Duration::from_millis(self.millis.saturating_sub(rhs.millis))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(value: &str) -> Timestamp {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_every_accepted_form() {
+        assert_eq!(ts("01:02:03.456"), Timestamp::from_millis(3_723_456));
+        assert_eq!(ts("01:02:03,456"), Timestamp::from_millis(3_723_456));
+        assert_eq!(ts("02:03.456"), Timestamp::from_millis(123_456));
+        assert_eq!(ts("01:02:03"), Timestamp::from_millis(3_723_000));
+        assert_eq!(ts("74.5"), Timestamp::from_millis(74_500));
+        assert_eq!(ts(" 0:00:01.5 "), Timestamp::from_millis(1_500));
+        // Extra fraction digits are truncated
+        assert_eq!(ts("00:00:01.2349"), Timestamp::from_millis(1_234));
+        // Hours past 99 are fine
+        assert_eq!(ts("100:00:00.000"), Timestamp::from_millis(360_000_000));
+    }
+
+    #[test]
+    fn rejects_out_of_range_and_malformed_values() {
+        for value in ["00:60:00.000", "00:00:60.000", "60.000:00", "1:2:3:4", "", "::", "-1", "00:0a:00", "00:00:01.5x", "00:00:01 000"] {
+            assert!(value.parse::<Timestamp>().is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_hours_that_overflow() {
+        assert!("5124095576030431:00:00.000".parse::<Timestamp>().is_err());
+        assert!("99999999999999999999:00:00".parse::<Timestamp>().is_err());
+        // The largest hour count that still fits
+        assert!("5124095576030:00:00.000".parse::<Timestamp>().is_ok());
+    }
+
+    #[test]
+    fn displays_as_hh_mm_ss_mmm() {
+        assert_eq!(Timestamp::from_millis(0).to_string(), "00:00:00.000");
+        assert_eq!(Timestamp::from_millis(3_723_456).to_string(), "01:02:03.456");
+        assert_eq!(Timestamp::from_millis(360_000_007).to_string(), "100:00:00.007");
+        assert_eq!(ts(&Timestamp::from_millis(59_999).to_string()), Timestamp::from_millis(59_999));
+    }
+
+    #[test]
+    fn converts_from_seconds() {
+        assert_eq!(Timestamp::from_secs_f64(1.2345), Timestamp::from_millis(1_235));
+        assert_eq!(Timestamp::from_secs_f64(-3.0), Timestamp::from_millis(0));
+    }
+
+    #[test]
+    fn adds_and_subtracts_without_overflow() {
+        let t = Timestamp::from_millis(1_000);
+
+        assert_eq!(t + Duration::from_millis(500), Timestamp::from_millis(1_500));
+        assert_eq!(t - Duration::from_millis(400), Timestamp::from_millis(600));
+        assert_eq!(t - Duration::from_secs(5), Timestamp::from_millis(0));
+        assert_eq!(Timestamp::from_millis(u64::MAX - 1) + Duration::from_secs(1), Timestamp::from_millis(u64::MAX));
+        assert_eq!(t + Duration::MAX, Timestamp::from_millis(u64::MAX));
+
+        assert_eq!(Timestamp::from_millis(2_500) - t, Duration::from_millis(1_500));
+        assert_eq!(t - Timestamp::from_millis(2_500), Duration::ZERO);
+        assert_eq!(t.abs_diff(Timestamp::from_millis(2_500)), Duration::from_millis(1_500));
+    }
+}
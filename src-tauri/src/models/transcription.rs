@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-job transcription choices from the UI
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TranscriptionOptions {
+    /// Spoken language code (e.g. "es"); `None` lets the backend detect it
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Produce an English transcript whatever the spoken language
+    #[serde(default)]
+    pub translate: bool,
+    /// Vocabulary hint passed to the model (filled in from the glossary)
+    #[serde(skip)]
+    pub prompt: Option<String>,
+}
+
+/// Generated transcript returned to the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionResult {
+    pub vtt_path: String,
+    /// Language of the transcript text (always "en" when translated)
+    pub language: Option<String>,
+    pub translated: bool,
+}
@@ -1,9 +1,31 @@
+use super::Timestamp;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// VTT cue structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VttCue {
-    pub start_timestamp: String,  // HH:MM:SS.mmm
-    pub end_timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    pub start_timestamp: Timestamp,
+    pub end_timestamp: Timestamp,
+    /// Cue settings such as `align` or `position`, keyed by setting name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub settings: BTreeMap<String, String>,
+    /// Speaker name from a `<v Name>` voice span, if the cue has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+    /// Cue text with inline markup removed
     pub text: String,
+    /// Per-word timings, when the transcript came with them (Whisper verbose JSON)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTiming>,
+}
+
+/// When a single word is spoken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    pub start: Timestamp,
+    pub end: Timestamp,
 }
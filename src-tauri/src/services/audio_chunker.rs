@@ -0,0 +1,258 @@
+use crate::models::Timestamp;
+use crate::services::CancelToken;
+use regex::Regex;
+use std::path::Path;
+use std::time::Duration;
+use tauri::api::process::{Command, CommandEvent};
+
+/// Quiet stretch reported by FFmpeg's `silencedetect` filter
+#[derive(Debug, Clone, Copy)]
+pub struct Silence {
+    pub start: Timestamp,
+    pub end: Timestamp,
+}
+
+/// One slice of a longer audio file
+///
+/// The audio covers `audio_start..audio_end`, which overlaps the neighbouring
+/// chunks. Cues are kept only if they fall inside `keep_start..keep_end`, so
+/// every moment of the recording is owned by exactly one chunk.
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    pub index: usize,
+    pub audio_start: Timestamp,
+    pub audio_end: Timestamp,
+    pub keep_start: Timestamp,
+    pub keep_end: Timestamp,
+    pub path: String,
+}
+
+/// Splits long audio files for APIs with upload limits
+pub struct AudioChunker;
+
+impl AudioChunker {
+    /// Measure the audio and find its silences in a single FFmpeg pass
+    pub async fn analyze(audio_path: &str, cancel: &CancelToken) -> Result<(Timestamp, Vec<Silence>), String> {
+        println!("=== Audio Chunking: Silence Detection ===");
+
+        let args = vec![
+            "-nostats",
+            "-i", audio_path,
+            "-af", "silencedetect=noise=-30dB:d=0.5",
+            "-f", "null",
+            "-",
+        ];
+        let output = Self::run_ffmpeg(&args, cancel).await?;
+
+        let duration_regex = Regex::new(r"Duration:\s*(\d+:\d{2}:\d{2}(?:\.\d+)?)").unwrap();
+        let start_regex = Regex::new(r"silence_start:\s*(-?[\d.]+)").unwrap();
+        let end_regex = Regex::new(r"silence_end:\s*(-?[\d.]+)").unwrap();
+
+        let mut duration = None;
+        let mut silences = Vec::new();
+        let mut silence_start = None;
+
+        for line in &output {
+            if duration.is_none() {
+                if let Some(captures) = duration_regex.captures(line) {
+                    duration = captures[1].parse::<Timestamp>().ok();
+                }
+            }
+            if let Some(captures) = start_regex.captures(line) {
+                silence_start = captures[1].parse::<f64>().ok().map(Timestamp::from_secs_f64);
+            }
+            if let Some(captures) = end_regex.captures(line) {
+                let end = captures[1].parse::<f64>().ok().map(Timestamp::from_secs_f64);
+                if let (Some(start), Some(end)) = (silence_start.take(), end) {
+                    silences.push(Silence { start, end });
+                }
+            }
+        }
+
+        let duration = duration.ok_or("Could not determine audio duration")?;
+        println!("Duration: {}, silences found: {}", duration, silences.len());
+
+        Ok((duration, silences))
+    }
+
+    /// Choose chunk boundaries near every `target` of audio
+    ///
+    /// Each cut goes in the middle of the latest silence within the last fifth
+    /// of the chunk (at most a minute back); without one, the cut is made at
+    /// exactly `target`. Chunk files are named after `audio_path`.
+    pub fn plan(
+        audio_path: &str,
+        duration: Timestamp,
+        silences: &[Silence],
+        target: Duration,
+        overlap: Duration,
+    ) -> Vec<AudioChunk> {
+        let target = target.max(Duration::from_secs(60));
+        let search = (target / 5).min(Duration::from_secs(60));
+
+        let mut boundaries = vec![Timestamp::default()];
+        let mut position = Timestamp::default();
+
+        // A short tail is folded into the last chunk rather than sent on its own
+        while duration - position > target + search {
+            let ideal = position + target;
+            let cut = silences
+                .iter()
+                .map(|silence| silence.start + (silence.end - silence.start) / 2)
+                .filter(|middle| *middle > ideal - search && *middle <= ideal)
+                .max()
+                .unwrap_or(ideal);
+
+            boundaries.push(cut);
+            position = cut;
+        }
+        boundaries.push(duration);
+
+        let path = Path::new(audio_path);
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("audio");
+        let directory = path.parent().unwrap_or_else(|| Path::new("."));
+
+        boundaries
+            .windows(2)
+            .enumerate()
+            .map(|(index, range)| AudioChunk {
+                index,
+                audio_start: range[0] - overlap,
+                audio_end: (range[1] + overlap).min(duration),
+                keep_start: range[0],
+                keep_end: range[1],
+                path: directory
+                    .join(format!("{}_chunk{}.mp3", stem, index + 1))
+                    .to_string_lossy()
+                    .to_string(),
+            })
+            .collect()
+    }
+
+    /// Write a chunk's audio to its own file
+    pub async fn extract(audio_path: &str, chunk: &AudioChunk, cancel: &CancelToken) -> Result<(), String> {
+        let start_arg = chunk.audio_start.to_ffmpeg();
+        let duration_arg = format!("{:.3}", (chunk.audio_end - chunk.audio_start).as_secs_f64());
+
+        // Re-encoding (cheap for 16 kHz mono) keeps the cut sample-accurate
+        let args = vec![
+            "-ss", &start_arg,
+            "-i", audio_path,
+            "-t", &duration_arg,
+            "-acodec", "libmp3lame",
+            "-ar", "16000",
+            "-ac", "1",
+            "-b:a", "64k",
+            "-y",
+            &chunk.path,
+        ];
+
+        println!("Extracting chunk {} ({} -> {})", chunk.index + 1, chunk.audio_start, chunk.audio_end);
+        Self::run_ffmpeg(&args, cancel).await.map(|_| ())
+    }
+
+    /// Run FFmpeg to completion and return its stderr lines
+    async fn run_ffmpeg(args: &[&str], cancel: &CancelToken) -> Result<Vec<String>, String> {
+        let (mut rx, child) = Command::new_sidecar("ffmpeg")
+            .map_err(|e| format!("Failed to find FFmpeg: {}", e))?
+            .args(args)
+            .spawn()
+            .map_err(|e| format!("Failed to spawn FFmpeg: {}", e))?;
+
+        let mut child = Some(child);
+        let mut stderr_lines = Vec::new();
+
+        while let Some(event) = cancel.next_event(&mut rx, &mut child).await? {
+            match event {
+                CommandEvent::Stderr(line) => stderr_lines.push(line),
+                CommandEvent::Error(error) => {
+                    return Err(format!("FFmpeg error: {}", error));
+                }
+                CommandEvent::Terminated(payload) => {
+                    if payload.code != Some(0) {
+                        println!("=== FULL STDERR ===");
+                        for line in &stderr_lines {
+                            println!("{}", line);
+                        }
+                        println!("=== END STDERR ===");
+                        return Err(format!("FFmpeg failed with code: {:?}", payload.code));
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(stderr_lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(seconds: u64) -> Timestamp {
+        Timestamp::from_millis(seconds * 1000)
+    }
+
+    fn silence(start: u64, end: u64) -> Silence {
+        Silence { start: secs(start), end: secs(end) }
+    }
+
+    fn ranges(chunks: &[AudioChunk]) -> Vec<(u64, u64, u64, u64)> {
+        let secs = |time: Timestamp| time.as_duration().as_secs();
+        chunks
+            .iter()
+            .map(|chunk| (secs(chunk.audio_start), secs(chunk.audio_end), secs(chunk.keep_start), secs(chunk.keep_end)))
+            .collect()
+    }
+
+    const TEN_MINUTES: Duration = Duration::from_secs(600);
+    const OVERLAP: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn keeps_short_audio_in_one_chunk() {
+        // Up to the target plus the search window isn't worth splitting
+        let chunks = AudioChunker::plan("/tmp/talk.mp3", secs(650), &[], TEN_MINUTES, OVERLAP);
+
+        assert_eq!(ranges(&chunks), vec![(0, 650, 0, 650)]);
+        assert_eq!(chunks[0].index, 0);
+    }
+
+    #[test]
+    fn cuts_in_silences_and_overlaps_neighbours() {
+        // 570 is the middle of a silence in the last minute before 600; 500 is too early
+        let silences = [silence(499, 501), silence(569, 571)];
+        let chunks = AudioChunker::plan("/tmp/talk.mp3", secs(1500), &silences, TEN_MINUTES, OVERLAP);
+
+        assert_eq!(
+            ranges(&chunks),
+            vec![
+                // The first chunk can't start before zero, and the last can't run past the end
+                (0, 572, 0, 570),
+                // No silence near 1170, so the cut is made there exactly
+                (568, 1172, 570, 1170),
+                (1168, 1500, 1170, 1500),
+            ]
+        );
+        let paths: Vec<&str> = chunks.iter().map(|chunk| chunk.path.as_str()).collect();
+        assert_eq!(paths, vec!["/tmp/talk_chunk1.mp3", "/tmp/talk_chunk2.mp3", "/tmp/talk_chunk3.mp3"]);
+    }
+
+    #[test]
+    fn prefers_the_latest_silence_before_the_target() {
+        let silences = [silence(549, 551), silence(589, 591), silence(620, 630)];
+        let chunks = AudioChunker::plan("talk.mp3", secs(1500), &silences, TEN_MINUTES, Duration::ZERO);
+
+        assert_eq!(chunks[0].keep_end, secs(590));
+        assert_eq!(chunks[1].keep_start, secs(590));
+    }
+
+    #[test]
+    fn raises_tiny_targets_to_a_minute() {
+        let chunks = AudioChunker::plan("talk.mp3", secs(200), &[], Duration::from_secs(5), Duration::ZERO);
+
+        // Cut every minute until no more than a minute plus the 12s search window is left
+        assert_eq!(ranges(&chunks), vec![(0, 60, 0, 60), (60, 120, 60, 120), (120, 180, 120, 180), (180, 200, 180, 200)]);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::models::{CaptionPosition, CaptionSettings, Timestamp, VttCue};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Captions are laid out on a frame this many pixels tall and scaled to the video
+const PLAY_RES_HEIGHT: u32 = 1080;
+
+/// Writes styled ASS subtitles for burning captions into a clip
+pub struct CaptionWriter;
+
+impl CaptionWriter {
+    /// ASS file for cues already re-timed to the clip (see `VttParser::clip_cues`)
+    ///
+    /// `frame` is the output resolution, which sets the layout's aspect ratio.
+    /// With `word_highlight` on, cues that have word timings get one event per
+    /// word, showing the whole line with the current word in the highlight colour.
+    pub fn to_ass(cues: &[VttCue], style: &CaptionSettings, frame: (u32, u32)) -> String {
+        let alignment = match style.position {
+            CaptionPosition::Bottom => 2,
+            CaptionPosition::Middle => 5,
+            CaptionPosition::Top => 8,
+        };
+        let mut ass = Self::header(style, frame, style.font_size, alignment);
+
+        let highlight = Self::colour(&style.highlight_colour, "00D4FF");
+        for cue in cues {
+            let words: Vec<&str> = cue.words
+                .iter()
+                .map(|word| word.word.trim())
+                .filter(|word| !word.is_empty())
+                .collect();
+
+            if !style.word_highlight || words.len() != cue.words.len() || words.is_empty() {
+                Self::event(&mut ass, cue.start_timestamp, cue.end_timestamp, &Self::escape(&cue.text));
+                continue;
+            }
+
+            // Before the first word the line shows without a highlight
+            if cue.start_timestamp < cue.words[0].start {
+                Self::event(&mut ass, cue.start_timestamp, cue.words[0].start, &Self::escape(&words.join(" ")));
+            }
+
+            // Each word stays highlighted until the next one starts, so the line never flickers
+            for (index, word) in cue.words.iter().enumerate() {
+                let end = cue.words
+                    .get(index + 1)
+                    .map(|next| next.start)
+                    .unwrap_or(cue.end_timestamp)
+                    .max(word.end.min(cue.end_timestamp));
+                let text = words
+                    .iter()
+                    .enumerate()
+                    .map(|(i, text)| {
+                        if i == index {
+                            format!("{{\\c&H{}&}}{}{{\\r}}", highlight, Self::escape(text))
+                        } else {
+                            Self::escape(text)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                Self::event(&mut ass, word.start, end, &text);
+            }
+        }
+
+        ass
+    }
+
+    /// ASS file showing `title` centered for `duration`, fading in and out
+    ///
+    /// Uses the caption font and colours at one and a half times the caption size.
+    pub fn title_card(title: &str, style: &CaptionSettings, frame: (u32, u32), duration: Duration) -> String {
+        let mut ass = Self::header(style, frame, style.font_size * 3 / 2, 5);
+        let fade = (duration.as_millis() as u64 / 4).min(400);
+        Self::event(
+            &mut ass,
+            Timestamp::default(),
+            Timestamp::from_millis(duration.as_millis() as u64),
+            &format!("{{\\fad({},{})}}{}", fade, fade, Self::escape(title)),
+        );
+        ass
+    }
+
+    /// Script info, the single `Default` style and the events header
+    fn header(style: &CaptionSettings, frame: (u32, u32), font_size: u32, alignment: u8) -> String {
+        let (width, height) = frame;
+        let play_res_width = (PLAY_RES_HEIGHT as f64 * width as f64 / height.max(1) as f64).round() as u32;
+
+        let mut ass = String::new();
+        let _ = writeln!(ass, "[Script Info]");
+        let _ = writeln!(ass, "ScriptType: v4.00+");
+        let _ = writeln!(ass, "PlayResX: {}", play_res_width);
+        let _ = writeln!(ass, "PlayResY: {}", PLAY_RES_HEIGHT);
+        let _ = writeln!(ass, "WrapStyle: 0");
+        let _ = writeln!(ass, "ScaledBorderAndShadow: yes");
+        let _ = writeln!(ass);
+        let _ = writeln!(ass, "[V4+ Styles]");
+        let _ = writeln!(
+            ass,
+            "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, \
+             Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, \
+             Alignment, MarginL, MarginR, MarginV, Encoding"
+        );
+        let _ = writeln!(
+            ass,
+            "Style: Default,{},{},&H00{},&H00{},&H00{},&H80000000,{},0,0,0,100,100,0,0,1,{},0,{},60,60,{},1",
+            style.font.replace(',', " "),
+            font_size,
+            Self::colour(&style.colour, "FFFFFF"),
+            Self::colour(&style.highlight_colour, "00D4FF"),
+            Self::colour(&style.outline_colour, "000000"),
+            if style.bold { -1 } else { 0 },
+            style.outline_width,
+            alignment,
+            style.margin
+        );
+        let _ = writeln!(ass);
+        let _ = writeln!(ass, "[Events]");
+        let _ = writeln!(ass, "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text");
+        ass
+    }
+
+    fn event(ass: &mut String, start: Timestamp, end: Timestamp, text: &str) {
+        if end <= start {
+            return;
+        }
+        let _ = writeln!(
+            ass,
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}",
+            Self::time(start),
+            Self::time(end),
+            text
+        );
+    }
+
+    /// ASS times are `H:MM:SS.cc` (centiseconds)
+    fn time(timestamp: Timestamp) -> String {
+        let centis = timestamp.as_duration().as_millis() as u64 / 10;
+        format!(
+            "{}:{:02}:{:02}.{:02}",
+            centis / 360_000,
+            (centis / 6_000) % 60,
+            (centis / 100) % 60,
+            centis % 100
+        )
+    }
+
+    /// `#RRGGBB` -> `BBGGRR`, the order ASS colours are written in
+    fn colour(hex: &str, fallback: &str) -> String {
+        let hex = hex.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            println!("⚠️  Invalid caption colour '{}', using {}", hex, fallback);
+            return fallback.to_string();
+        }
+        format!("{}{}{}", &hex[4..6], &hex[2..4], &hex[0..2]).to_uppercase()
+    }
+
+    /// Keep cue text from being read as override tags
+    fn escape(text: &str) -> String {
+        text.replace('\\', "\u{29F5}")
+            .replace('{', "(")
+            .replace('}', ")")
+            .replace('\n', "\\N")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WordTiming;
+
+    fn ms(millis: u64) -> Timestamp {
+        Timestamp::from_millis(millis)
+    }
+
+    fn word(word: &str, start: u64, end: u64) -> WordTiming {
+        WordTiming { word: word.to_string(), start: ms(start), end: ms(end) }
+    }
+
+    fn cue(start: u64, end: u64, text: &str, words: Vec<WordTiming>) -> VttCue {
+        VttCue {
+            start_timestamp: ms(start),
+            end_timestamp: ms(end),
+            text: text.to_string(),
+            words,
+            ..Default::default()
+        }
+    }
+
+    fn events(ass: &str) -> Vec<&str> {
+        ass.lines().filter(|line| line.starts_with("Dialogue: ")).collect()
+    }
+
+    #[test]
+    fn writes_the_style_header() {
+        let ass = CaptionWriter::to_ass(&[], &CaptionSettings::default(), (1080, 1920));
+
+        // Laid out 1080 pixels tall at the frame's aspect ratio
+        assert!(ass.contains("PlayResX: 608\nPlayResY: 1080\n"));
+        assert!(ass.contains(
+            "Style: Default,Arial,64,&H00FFFFFF,&H0000D4FF,&H00000000,&H80000000,-1,0,0,0,100,100,0,0,1,4,0,2,60,60,120,1\n"
+        ));
+        assert!(ass.ends_with("[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n"));
+
+        let style = CaptionSettings {
+            font: "Inter, Bold".to_string(),
+            bold: false,
+            position: CaptionPosition::Top,
+            ..Default::default()
+        };
+        let ass = CaptionWriter::to_ass(&[], &style, (1920, 1080));
+        assert!(ass.contains("PlayResX: 1920\n"));
+        assert!(ass.contains("Style: Default,Inter  Bold,64,"));
+        assert!(ass.contains(",0,0,0,0,100,100,0,0,1,4,0,8,60,60,120,1\n"));
+    }
+
+    #[test]
+    fn converts_colours_to_ass_order() {
+        assert_eq!(CaptionWriter::colour("#FF8000", "FFFFFF"), "0080FF");
+        assert_eq!(CaptionWriter::colour(" 12abef ", "FFFFFF"), "EFAB12");
+        assert_eq!(CaptionWriter::colour("#FFF", "FFFFFF"), "FFFFFF");
+        assert_eq!(CaptionWriter::colour("#GG0000", "000000"), "000000");
+    }
+
+    #[test]
+    fn escapes_override_characters() {
+        assert_eq!(CaptionWriter::escape("{\\b1}bold{\\b0}"), "(\u{29F5}b1)bold(\u{29F5}b0)");
+        assert_eq!(CaptionWriter::escape("two\nlines"), "two\\Nlines");
+    }
+
+    #[test]
+    fn formats_times_in_centiseconds() {
+        assert_eq!(CaptionWriter::time(ms(0)), "0:00:00.00");
+        assert_eq!(CaptionWriter::time(ms(999)), "0:00:00.99");
+        assert_eq!(CaptionWriter::time(ms(3_723_456)), "1:02:03.45");
+        assert_eq!(CaptionWriter::time(ms(36_000_000)), "10:00:00.00");
+    }
+
+    #[test]
+    fn writes_one_event_per_cue_without_word_timings() {
+        let cues = [
+            cue(0, 2_500, "First {line}", vec![]),
+            cue(2_500, 2_500, "Empty", vec![]),
+            cue(3_000, 5_000, "Second\nline", vec![]),
+        ];
+        let ass = CaptionWriter::to_ass(&cues, &CaptionSettings::default(), (1920, 1080));
+
+        assert_eq!(
+            events(&ass),
+            [
+                "Dialogue: 0,0:00:00.00,0:00:02.50,Default,,0,0,0,,First (line)",
+                "Dialogue: 0,0:00:03.00,0:00:05.00,Default,,0,0,0,,Second\\Nline",
+            ]
+        );
+    }
+
+    #[test]
+    fn highlights_each_word_as_it_is_spoken() {
+        let cues = [cue(1_000, 3_000, "Hello there", vec![word(" Hello", 1_200, 1_600), word(" there", 1_700, 2_500)])];
+        let style = CaptionSettings { highlight_colour: "#FF0000".to_string(), ..Default::default() };
+        let ass = CaptionWriter::to_ass(&cues, &style, (1080, 1920));
+
+        // A word stays lit until the next starts; the last one until the cue ends
+        assert_eq!(
+            events(&ass),
+            [
+                "Dialogue: 0,0:00:01.00,0:00:01.20,Default,,0,0,0,,Hello there",
+                "Dialogue: 0,0:00:01.20,0:00:01.70,Default,,0,0,0,,{\\c&H0000FF&}Hello{\\r} there",
+                "Dialogue: 0,0:00:01.70,0:00:03.00,Default,,0,0,0,,Hello {\\c&H0000FF&}there{\\r}",
+            ]
+        );
+
+        let plain = CaptionWriter::to_ass(&cues, &CaptionSettings { word_highlight: false, ..style }, (1080, 1920));
+        assert_eq!(events(&plain), ["Dialogue: 0,0:00:01.00,0:00:03.00,Default,,0,0,0,,Hello there"]);
+    }
+
+    #[test]
+    fn shows_the_whole_cue_when_a_word_is_blank() {
+        let cues = [cue(0, 2_000, "Um, okay", vec![word("Um,", 0, 400), word(" ", 400, 600), word("okay", 600, 1_500)])];
+        let ass = CaptionWriter::to_ass(&cues, &CaptionSettings::default(), (1920, 1080));
+
+        assert_eq!(events(&ass), ["Dialogue: 0,0:00:00.00,0:00:02.00,Default,,0,0,0,,Um, okay"]);
+    }
+
+    #[test]
+    fn writes_title_cards() {
+        let ass = CaptionWriter::title_card("Part {2}", &CaptionSettings::default(), (1920, 1080), Duration::from_secs(3));
+
+        assert!(ass.contains("Style: Default,Arial,96,"));
+        assert!(ass.contains(",1,4,0,5,60,60,120,1\n"));
+        assert_eq!(events(&ass), ["Dialogue: 0,0:00:00.00,0:00:03.00,Default,,0,0,0,,{\\fad(400,400)}Part (2)"]);
+
+        // Short cards fade for a quarter of their length each way
+        let short = CaptionWriter::title_card("Hi", &CaptionSettings::default(), (1920, 1080), Duration::from_millis(800));
+        assert!(events(&short)[0].ends_with("{\\fad(200,200)}Hi"));
+    }
+}
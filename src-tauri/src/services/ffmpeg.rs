@@ -1,19 +1,249 @@
-use crate::models::{ValidatedClip, ClipProgress};
+use crate::models::{ValidatedClip, CaptionSettings, ClipProgress, ClipResult, ExportMode, ExportProfile, ExportSettings, MediaInfo, Reframe, ReframeFit, SubtitleFormat, Timestamp, VideoStreamInfo, VttCue};
+use crate::services::captions::CaptionWriter;
+use crate::services::jobs::JOB_CANCELLED;
+use crate::services::{CancelToken, FfmpegArgs, MediaInfoService, SrtParser, VttParser};
 use std::path::Path;
 use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::api::process::{Command, CommandEvent};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 pub struct FFmpegService;
 
+/// A keyframe this close to the requested start counts as on it
+const KEYFRAME_TOLERANCE: Duration = Duration::from_millis(20);
+
+/// Smart render falls back to a full re-encode when less than this could be copied
+const SMART_RENDER_MIN_TAIL: Duration = Duration::from_secs(1);
+
+/// Encoder that produces a source codec, for smart-render heads
+fn matching_encoder(codec: &str) -> Option<&'static str> {
+    match codec {
+        "h264" => Some("libx264"),
+        "hevc" => Some("libx265"),
+        _ => None,
+    }
+}
+
+/// Encoder options that make a smart-render head decodable with the source's
+/// parameter sets: the same profile and level (ffprobe's names and numbers)
+fn matching_profile_and_level(video: &VideoStreamInfo) -> Vec<String> {
+    let mut args = Vec::new();
+    match video.codec.as_str() {
+        "h264" => {
+            let profile = match video.profile.as_deref() {
+                Some("Baseline" | "Constrained Baseline") => Some("baseline"),
+                Some("Main") => Some("main"),
+                Some("High") => Some("high"),
+                Some("High 10") => Some("high10"),
+                Some("High 4:2:2") => Some("high422"),
+                Some("High 4:4:4 Predictive") => Some("high444"),
+                _ => None,
+            };
+            if let Some(profile) = profile {
+                args.extend(["-profile:v".to_string(), profile.to_string()]);
+            }
+            // 41 -> 4.1
+            if let Some(level) = video.level {
+                args.extend(["-level".to_string(), format!("{}.{}", level / 10, level % 10)]);
+            }
+        }
+        "hevc" => {
+            let profile = match video.profile.as_deref() {
+                Some("Main") => Some("main"),
+                Some("Main 10") => Some("main10"),
+                _ => None,
+            };
+            if let Some(profile) = profile {
+                args.extend(["-profile:v".to_string(), profile.to_string()]);
+            }
+            // general_level_idc is 30 times the level: 123 -> 4.1; libx265 has no -level
+            if let Some(level) = video.level {
+                let level = level / 3;
+                args.extend(["-x265-params".to_string(), format!("level-idc={}.{}", level / 10, level % 10)]);
+            }
+        }
+        _ => {}
+    }
+    args
+}
+
+/// Transcript lines for the clips, burned in and/or written beside them
+pub struct ClipSubtitles {
+    pub cues: Vec<VttCue>,
+    /// Transcript language, for the WebVTT header
+    pub language: Option<String>,
+    /// Burn captions in with this style
+    pub burn_in: Option<CaptionSettings>,
+    /// Subtitle files to write next to each clip
+    pub files: Vec<SubtitleFormat>,
+}
+
+/// How one clip worker encodes its clip
+#[derive(Clone)]
+struct EncodeOptions {
+    mode: ExportMode,
+    profile: ExportProfile,
+    threads: u32,
+    source: Option<MediaInfo>, // None when not probed (re-encode mode, or the probe failed)
+    reframe: Option<Reframe>,
+    captions: Option<String>, // ASS subtitles timed from the clip's start
+    fade_in: Duration,
+    fade_out: Duration,
+    comment: Option<String>,  // Written to the file's metadata
+}
+
+impl EncodeOptions {
+    /// Tag the output with `comment`, if there is one
+    fn comment(&self, args: FfmpegArgs) -> FfmpegArgs {
+        match &self.comment {
+            Some(comment) => args.metadata("comment", comment),
+            None => args,
+        }
+    }
+}
+
+/// How a single clip gets written
+#[derive(Debug)]
+enum CutPlan {
+    Reencode,
+    /// Copy the streams, starting on the keyframe at `start`
+    Copy { start: Timestamp },
+    /// Re-encode up to `keyframe`, copy the rest
+    SmartRender { keyframe: Timestamp },
+}
+
+/// Reads FFmpeg's `-progress` output, which comes in `key=value` blocks
+/// that each end in `progress=continue` (or `progress=end`)
+#[derive(Default)]
+pub(crate) struct ProgressReader {
+    encoded: Duration,
+    speed: Option<f32>,
+}
+
+impl ProgressReader {
+    /// Take one line; at the end of a block, returns output time encoded so far and encode speed
+    pub(crate) fn read_line(&mut self, line: &str) -> Option<(Duration, Option<f32>)> {
+        match line.trim().split_once('=') {
+            Some(("out_time_us", value)) | Some(("out_time_ms", value)) => {
+                // Both keys are in microseconds; early blocks can be negative or N/A
+                if let Ok(micros) = value.parse::<i64>() {
+                    self.encoded = Duration::from_micros(micros.max(0) as u64);
+                }
+            }
+            Some(("speed", value)) => {
+                self.speed = value.trim_end_matches('x').trim().parse::<f32>().ok();
+            }
+            Some(("progress", _)) => return Some((self.encoded, self.speed)),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Encode progress shared by the clip workers
+pub(crate) struct ProgressTracker {
+    clip_durations: Vec<Duration>,
+    encoded: Vec<Duration>,
+    speeds: Vec<Option<f32>>,   // Latest speed of each clip still encoding
+    finished: usize,
+    last_speed: Option<f32>,    // Combined speed, kept for the gaps between clips
+}
+
+impl ProgressTracker {
+    pub(crate) fn new(clip_durations: Vec<Duration>) -> Self {
+        let count = clip_durations.len();
+        Self {
+            clip_durations,
+            encoded: vec![Duration::ZERO; count],
+            speeds: vec![None; count],
+            finished: 0,
+            last_speed: None,
+        }
+    }
+    
+    /// Record that `encoded` of clip `index` is done
+    pub(crate) fn update(&mut self, index: usize, encoded: Duration, speed: Option<f32>) -> ClipProgress {
+        self.encoded[index] = encoded.min(self.clip_durations[index]);
+        if speed.is_some() {
+            self.speeds[index] = speed;
+        }
+        self.event(index)
+    }
+    
+    /// Record that clip `index` is done with, whether it succeeded or not
+    pub(crate) fn finish(&mut self, index: usize) -> ClipProgress {
+        self.encoded[index] = self.clip_durations[index];
+        self.speeds[index] = None;
+        self.finished += 1;
+        self.event(index)
+    }
+    
+    fn event(&mut self, index: usize) -> ClipProgress {
+        let percent = |part: Duration, whole: Duration| {
+            if whole.is_zero() {
+                100.0
+            } else {
+                (part.as_secs_f32() / whole.as_secs_f32() * 100.0).min(100.0)
+            }
+        };
+        
+        // Workers run side by side, so their speeds add up
+        let active_speeds: Vec<f32> = self.speeds.iter().flatten().copied().collect();
+        if !active_speeds.is_empty() {
+            self.last_speed = Some(active_speeds.iter().sum());
+        }
+        
+        let total_duration: Duration = self.clip_durations.iter().sum();
+        let done: Duration = self.encoded.iter().sum();
+        let remaining = total_duration.saturating_sub(done);
+        let eta_seconds = self.last_speed
+            .filter(|speed| *speed > 0.0)
+            .map(|speed| (remaining.as_secs_f32() / speed).round() as u64);
+        let active = self.encoded
+            .iter()
+            .zip(&self.clip_durations)
+            .filter(|(encoded, duration)| !encoded.is_zero() && encoded < duration)
+            .count();
+        
+        ClipProgress {
+            current: self.finished,
+            total: self.clip_durations.len(),
+            active,
+            clip_index: index,
+            clip_percent: percent(self.encoded[index], self.clip_durations[index]),
+            overall_percent: percent(done, total_duration),
+            speed: self.last_speed,
+            eta_seconds,
+        }
+    }
+}
+
 impl FFmpegService {
     /// Generate clips from a video file
+    ///
+    /// Each clip is written with its own export profile if it has one, else
+    /// with `job_profile`, else with the default profile. Up to `settings.max_concurrent_clips` clips are encoded at once. A clip
+    /// that fails is reported in its `ClipResult` and the rest carry on; the
+    /// whole batch only fails if no clip could be generated. On cancellation
+    /// the clips being encoded are deleted and finished ones are kept.
+    /// With `subtitles`, the transcript lines heard in each clip are burned in
+    /// and/or saved beside it as `N_title.vtt`/`.srt`, timed from the clip's start.
+    /// With `settings.handle_seconds`, each file also holds that much of the
+    /// source before and after the clip (and the fades are left off).
     pub async fn generate_clips<F>(
         video_path: String,
         clips: Vec<ValidatedClip>,
+        settings: &ExportSettings,
+        job_profile: Option<&str>,
+        subtitles: Option<&ClipSubtitles>,
+        cancel: &CancelToken,
         progress_callback: F,
-    ) -> Result<String, String>
+    ) -> Result<(String, Vec<ClipResult>), String>
     where
-        F: Fn(ClipProgress),
+        F: Fn(ClipProgress) + Send + Sync + 'static,
     {
         // Create output directory
         let video_path_obj = Path::new(&video_path);
@@ -30,35 +260,227 @@ impl FFmpegService {
         fs::create_dir_all(&output_dir)
             .map_err(|e| format!("Failed to create output directory: {}", e))?;
         
-        // Process each clip
-        for (index, clip) in clips.iter().enumerate() {
+        let workers = settings.max_concurrent_clips.max(1);
+        println!("=== Generating {} clips with {} workers ({:?}) ===", clips.len(), workers, settings.mode);
+        
+        // Copy modes need the source codecs, captions and crop rectangles the
+        // frame size and handles the duration; if probing fails every clip is
+        // re-encoded, captions assume 1080p, crops aren't checked and handles
+        // may run past the end
+        let burn_in = subtitles.and_then(|subtitles| subtitles.burn_in.as_ref());
+        let handles = Duration::from_secs(settings.handle_seconds as u64);
+        let crops = clips
+            .iter()
+            .any(|clip| matches!(&clip.reframe, Some(Reframe { fit: ReframeFit::Crop { .. }, .. })));
+        let source = match (settings.mode, burn_in) {
+            (ExportMode::Reencode, None) if handles.is_zero() && !crops => None,
+            _ => match MediaInfoService::probe(&video_path).await {
+                Ok(source) => Some(source),
+                Err(e) => {
+                    println!("⚠️  Could not probe source ({}), re-encoding all clips", e);
+                    None
+                }
+            },
+        };
+        
+        // Handles make each file longer than its clip, up to the ends of the source
+        let cuts: Vec<(Timestamp, Timestamp)> = clips
+            .iter()
+            .map(|clip| {
+                let end = clip.end_time + handles;
+                let end = source.as_ref().map(|source| end.min(source.duration)).unwrap_or(end);
+                (clip.start_time - handles, end.max(clip.end_time))
+            })
+            .collect();
+        let (fade_in, fade_out) = if handles.is_zero() {
+            (Duration::from_millis(settings.fade_in_ms), Duration::from_millis(settings.fade_out_ms))
+        } else {
+            if settings.fade_in_ms > 0 || settings.fade_out_ms > 0 {
+                println!("⚠️  Exporting with handles, leaving out fades");
+            }
+            (Duration::ZERO, Duration::ZERO)
+        };
+        
+        // Overall progress is measured in seconds of output, not clip count
+        let tracker = Arc::new(Mutex::new(ProgressTracker::new(
+            cuts.iter().map(|(start, end)| *end - *start).collect(),
+        )));
+        let progress_callback = Arc::new(progress_callback);
+        let semaphore = Arc::new(Semaphore::new(workers));
+        let mut tasks = JoinSet::new();
+        
+        for (index, (clip, (cut_start, cut_end))) in clips.iter().zip(cuts).enumerate() {
+            let profile = settings.profile(clip.profile_id.as_deref().or(job_profile));
+            let clip_cues = subtitles
+                .map(|subtitles| VttParser::clip_cues(&subtitles.cues, cut_start, cut_end))
+                .unwrap_or_default();
+            // Fades can take at most half the clip each
+            let half = (cut_end - cut_start) / 2;
+            let options = EncodeOptions {
+                mode: settings.mode,
+                captions: match burn_in {
+                    Some(style) if !profile.is_audio_only() && !clip_cues.is_empty() => {
+                        Some(Self::clip_captions(clip, &clip_cues, style, source.as_ref()))
+                    }
+                    _ => None,
+                },
+                profile,
+                threads: settings.threads_per_clip,
+                source: source.clone(),
+                reframe: clip.reframe.clone(),
+                fade_in: fade_in.min(half),
+                fade_out: fade_out.min(half),
+                comment: (!handles.is_zero()).then(|| Self::handles_comment(clip, cut_start, cut_end)),
+            };
             let output_file = output_dir.join(format!(
-                "{}_{}.mp4",
+                "{}_{}.{}",
                 index + 1,
-                clip.sanitized_filename
+                clip.sanitized_filename,
+                options.profile.extension
             ));
             
-            Self::extract_clip(
-                &video_path,
-                &clip.start_time,
-                &clip.end_time,
-                output_file.to_str().unwrap(),
-            ).await?;
-            
-            progress_callback(ClipProgress {
-                current: index + 1,
-                total: clips.len(),
+            let semaphore = semaphore.clone();
+            let tracker = tracker.clone();
+            let progress_callback = progress_callback.clone();
+            let cancel = cancel.clone();
+            let video_path = video_path.clone();
+            let clip = clip.clone();
+            let subtitle_files = subtitles.map(|subtitles| subtitles.files.clone()).unwrap_or_default();
+            let language = subtitles.and_then(|subtitles| subtitles.language.clone());
+            tasks.spawn(async move {
+                let result = match semaphore.acquire_owned().await {
+                    Ok(_permit) if !cancel.is_cancelled() => {
+                        let report = |encoded, speed| {
+                            let event = tracker.lock().unwrap().update(index, encoded, speed);
+                            progress_callback(event);
+                        };
+                        Self::extract_clip(
+                            &video_path,
+                            cut_start,
+                            cut_end,
+                            output_file.to_str().unwrap(),
+                            &options,
+                            &cancel,
+                            report,
+                        ).await
+                    }
+                    // Clips still queued when the job is cancelled never start
+                    Ok(_) => Err(JOB_CANCELLED.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                
+                match &result {
+                    Ok(_) => Self::write_subtitle_files(&output_file, &clip_cues, &subtitle_files, language.as_deref()),
+                    Err(_) => {
+                        let _ = fs::remove_file(&output_file);
+                    }
+                }
+                let event = tracker.lock().unwrap().finish(index);
+                progress_callback(event);
+                
+                let clip_result = match result {
+                    Ok(_) => ClipResult {
+                        id: clip.id,
+                        title: clip.title,
+                        output_path: Some(output_file.to_string_lossy().to_string()),
+                        error: None,
+                    },
+                    Err(e) => ClipResult {
+                        id: clip.id,
+                        title: clip.title,
+                        output_path: None,
+                        error: Some(e),
+                    },
+                };
+                (index, clip_result)
             });
         }
         
-        Ok(output_dir.to_str().unwrap().to_string())
+        // Every worker is awaited, even after a cancel, so no FFmpeg is left running
+        let mut results: Vec<Option<ClipResult>> = (0..clips.len()).map(|_| None).collect();
+        while let Some(joined) = tasks.join_next().await {
+            let (index, clip_result) = joined.map_err(|e| format!("Clip task failed: {}", e))?;
+            if let Some(error) = &clip_result.error {
+                println!("⚠️  Clip {} failed: {}", index + 1, error);
+            }
+            results[index] = Some(clip_result);
+        }
+        let results: Vec<ClipResult> = results.into_iter().flatten().collect();
+        
+        let succeeded = results.iter().filter(|result| result.error.is_none()).count();
+        if cancel.is_cancelled() || succeeded == 0 {
+            // Only removed if no clip made it
+            let _ = fs::remove_dir(&output_dir);
+        }
+        if cancel.is_cancelled() {
+            return Err(JOB_CANCELLED.to_string());
+        }
+        if succeeded == 0 {
+            let first_error = results.iter().find_map(|result| result.error.clone()).unwrap_or_default();
+            return Err(format!("No clips could be generated. {}", first_error));
+        }
+        
+        println!("✅ Generated {} of {} clips", succeeded, results.len());
+        Ok((output_dir.to_str().unwrap().to_string(), results))
+    }
+    
+    /// Where the clip really starts and ends in a file exported with handles
+    fn handles_comment(clip: &ValidatedClip, cut_start: Timestamp, cut_end: Timestamp) -> String {
+        format!(
+            "Clip in {} out {} (handles {:.3}s before, {:.3}s after; source {} to {})",
+            Timestamp::from_millis((clip.start_time - cut_start).as_millis() as u64),
+            Timestamp::from_millis((clip.end_time - cut_start).as_millis() as u64),
+            (clip.start_time - cut_start).as_secs_f64(),
+            (cut_end - clip.end_time).as_secs_f64(),
+            clip.start_time,
+            clip.end_time
+        )
+    }
+    
+    /// ASS subtitles for one clip's (re-timed) cues, laid out for its output frame
+    fn clip_captions(clip: &ValidatedClip, cues: &[VttCue], style: &CaptionSettings, source: Option<&MediaInfo>) -> String {
+        let frame = match (&clip.reframe, source.and_then(|source| source.video.as_ref())) {
+            (Some(reframe), _) => reframe.aspect.output_size(),
+            (None, Some(video)) if video.width > 0 && video.height > 0 => (video.width, video.height),
+            _ => (1920, 1080),
+        };
+        CaptionWriter::to_ass(cues, style, frame)
+    }
+    
+    /// Save a clip's cues beside it as `N_title.vtt` / `N_title.srt`
+    ///
+    /// Clips with nothing said in them get no files. Failures are only
+    /// logged, since the clip itself was written.
+    fn write_subtitle_files(output_file: &Path, cues: &[VttCue], formats: &[SubtitleFormat], language: Option<&str>) {
+        if cues.is_empty() {
+            return;
+        }
+        
+        for format in formats {
+            let content = match format {
+                SubtitleFormat::Vtt => VttParser::to_vtt(cues, language),
+                SubtitleFormat::Srt => SrtParser::to_srt(cues),
+            };
+            let path = output_file.with_extension(format.extension());
+            match fs::write(&path, content) {
+                Ok(_) => println!("Subtitles: {}", path.display()),
+                Err(e) => println!("⚠️  Failed to write {}: {}", path.display(), e),
+            }
+        }
     }
     
+    /// Encode one clip, reporting output time encoded so far and encode speed
+    ///
+    /// How the clip is cut depends on `options.mode` and where the source's
+    /// keyframes are; see `plan_cut`.
     async fn extract_clip(
         input_path: &str,
-        start_time: &str,
-        end_time: &str,
+        start_time: Timestamp,
+        end_time: Timestamp,
         output_path: &str,
+        options: &EncodeOptions,
+        cancel: &CancelToken,
+        on_progress: impl Fn(Duration, Option<f32>),
     ) -> Result<(), String> {
         // LOG: Command details
         println!("=== FFmpeg Clip Extraction ===");
@@ -66,25 +488,315 @@ impl FFmpegService {
         println!("Start: {}", start_time);
         println!("End: {}", end_time);
         println!("Output: {}", output_path);
+        println!("Profile: {}", options.profile.name);
+        if let Some(reframe) = &options.reframe {
+            println!("Reframe: {:?}", reframe);
+        }
+        if let Some(comment) = &options.comment {
+            println!("Comment: {}", comment);
+        }
         
-        // Use H.264 encoding instead of stream copy to support all codecs (ProRes, etc.)
-        let args = vec![
-            "-i", input_path,
-            "-ss", start_time,
-            "-to", end_time,
-            "-c:v", "libx264",      // H.264 video codec (universal compatibility)
-            "-preset", "fast",       // Encoding speed (fast, medium, slow)
-            "-crf", "23",            // Quality: 18-28 (lower = better, 23 = default)
-            "-c:a", "aac",           // AAC audio codec
-            "-b:a", "192k",          // Audio bitrate
-            "-movflags", "+faststart", // Enable streaming
-            "-y",                    // Overwrite output
-            output_path,
-        ];
+        if let Some(reframe) = options.reframe.as_ref().filter(|_| !options.profile.is_audio_only()) {
+            let source_size = options.source
+                .as_ref()
+                .and_then(|source| source.video.as_ref())
+                .map(|video| (video.width, video.height));
+            reframe.validate(source_size)?;
+        }
+        
+        let plan = Self::plan_cut(start_time, end_time, options, cancel).await?;
+        println!("Cut plan: {:?}", plan);
+        
+        match (plan, &options.source) {
+            (CutPlan::Copy { start }, Some(source)) => {
+                let args = Self::copy_args(input_path, start, end_time, source, &options.profile);
+                let args = options.comment(args).build(output_path);
+                Self::run_ffmpeg(args, options.threads, cancel, on_progress).await
+            }
+            (CutPlan::SmartRender { keyframe }, Some(source)) => {
+                Self::smart_render(input_path, start_time, keyframe, end_time, output_path, source, options, cancel, on_progress).await
+            }
+            _ => {
+                let subtitles_path = format!("{}.ass", output_path);
+                if let Some(subtitles) = &options.captions {
+                    fs::write(&subtitles_path, subtitles)
+                        .map_err(|e| format!("Failed to write captions: {}", e))?;
+                }
+                let fades = !options.fade_in.is_zero() || !options.fade_out.is_zero();
+                let args = if options.captions.is_some() || fades {
+                    // The subtitles and fade filters see input timestamps, so seek the
+                    // input to start the clip at zero (still frame-accurate when re-encoding)
+                    FfmpegArgs::new()
+                        .seek_input(start_time)
+                        .input(input_path)
+                        .duration(end_time - start_time)
+                } else {
+                    // FFmpeg accepts HH:MM:SS.mmm, so cuts keep millisecond precision
+                    FfmpegArgs::new()
+                        .input(input_path)
+                        .trim_output(start_time, end_time)
+                };
+                let mut args = args.profile(&options.profile);
+                if !options.profile.is_audio_only() {
+                    if let Some(reframe) = &options.reframe {
+                        args = args.reframe(reframe);
+                    }
+                    // After reframing, so captions are laid out on the final frame
+                    if options.captions.is_some() {
+                        args = args.subtitles(&subtitles_path);
+                    }
+                }
+                // Last, so captions fade with the picture
+                let args = args.fades(options.fade_in, options.fade_out, end_time - start_time, &options.profile);
+                
+                let result = Self::run_ffmpeg(options.comment(args).build(output_path), options.threads, cancel, on_progress).await;
+                if options.captions.is_some() {
+                    let _ = fs::remove_file(&subtitles_path);
+                }
+                result
+            }
+        }
+    }
+    
+    /// Decide whether a clip can be stream-copied
+    ///
+    /// A copied clip has to begin on a keyframe, since the frames after it
+    /// only make sense with the keyframe they're predicted from. The end can
+    /// be cut anywhere: copying simply stops at the last frame before it.
+    async fn plan_cut(
+        start_time: Timestamp,
+        end_time: Timestamp,
+        options: &EncodeOptions,
+        cancel: &CancelToken,
+    ) -> Result<CutPlan, String> {
+        let source = match (&options.mode, &options.source) {
+            (ExportMode::Reencode, _) | (_, None) => return Ok(CutPlan::Reencode),
+            (_, Some(source)) => source,
+        };
+        
+        // Audio-only profiles are cheap to encode and have nothing to copy
+        if options.profile.is_audio_only() {
+            return Ok(CutPlan::Reencode);
+        }
+        
+        // Cropping, scaling, captions and fades touch every frame
+        let fades = !options.fade_in.is_zero() || !options.fade_out.is_zero();
+        if options.reframe.is_some() || options.captions.is_some() || fades {
+            return Ok(CutPlan::Reencode);
+        }
+        
+        // A copy keeps the source's codec, so only copy into profiles that encode to it
+        let video_codec = source.video_codec().unwrap_or("unknown");
+        if options.profile.video_codec() != Some(video_codec) {
+            println!(
+                "⚠️  {} video doesn't match {} ({}), re-encoding",
+                video_codec,
+                options.profile.name,
+                options.profile.video_codec().unwrap_or("no video")
+            );
+            return Ok(CutPlan::Reencode);
+        }
+        if !options.profile.can_hold_video(video_codec) {
+            println!(
+                "⚠️  {} video can't be copied into .{}, re-encoding",
+                video_codec, options.profile.extension
+            );
+            return Ok(CutPlan::Reencode);
+        }
+        
+        // Keyframes from just before the start to well past it (GOPs are rarely over 10s)
+        let keyframes = match MediaInfoService::keyframes(
+            source,
+            start_time - Duration::from_secs(1),
+            end_time.min(start_time + Duration::from_secs(20)),
+            cancel,
+        ).await {
+            Ok(keyframes) => keyframes,
+            Err(e) if cancel.is_cancelled() => return Err(e),
+            Err(e) => {
+                println!("⚠️  Could not read keyframes, re-encoding: {}", e);
+                return Ok(CutPlan::Reencode);
+            }
+        };
+        
+        if let Some(keyframe) = keyframes.iter().find(|keyframe| keyframe.abs_diff(start_time) <= KEYFRAME_TOLERANCE) {
+            return Ok(CutPlan::Copy { start: *keyframe });
+        }
+        
+        if options.mode == ExportMode::StreamCopy {
+            println!("Start {} is not on a keyframe, re-encoding", start_time);
+            return Ok(CutPlan::Reencode);
+        }
+        
+        // Smart render only pays off if most of the clip can still be copied
+        let next_keyframe = keyframes.into_iter().find(|keyframe| *keyframe > start_time);
+        match next_keyframe {
+            Some(keyframe) if keyframe + SMART_RENDER_MIN_TAIL < end_time && matching_encoder(video_codec).is_some() => {
+                Ok(CutPlan::SmartRender { keyframe })
+            }
+            _ => {
+                println!("No usable keyframe after {}, re-encoding", start_time);
+                Ok(CutPlan::Reencode)
+            }
+        }
+    }
+    
+    /// Re-encode from the start to the next keyframe, copy the rest, and join the two
+    ///
+    /// Both parts are written as MPEG-TS, whose Annex B streams carry their
+    /// parameter sets in-band, so the concat demuxer can join an encoded head
+    /// to a copied tail. The parts are video only: the audio is encoded in
+    /// one piece for the whole clip while joining, so there is no seam in it.
+    #[allow(clippy::too_many_arguments)]
+    async fn smart_render(
+        input_path: &str,
+        start_time: Timestamp,
+        keyframe: Timestamp,
+        end_time: Timestamp,
+        output_path: &str,
+        source: &MediaInfo,
+        options: &EncodeOptions,
+        cancel: &CancelToken,
+        on_progress: impl Fn(Duration, Option<f32>),
+    ) -> Result<(), String> {
+        let head_path = format!("{}.head.ts", output_path);
+        let tail_path = format!("{}.tail.ts", output_path);
+        let list_path = format!("{}.concat.txt", output_path);
+        let head_duration = keyframe - start_time;
+        let video_codec = source.video_codec().unwrap_or_default();
+        
+        let result = async {
+            let head_args = Self::head_args(input_path, start_time, keyframe, source, &head_path);
+            Self::run_ffmpeg(head_args, options.threads, cancel, &on_progress).await?;
+            
+            let tail_args = FfmpegArgs::new()
+                .seek_input(keyframe)
+                .input(input_path)
+                .duration(end_time - keyframe)
+                .option("-map", "0:v:0")
+                .option("-c:v", "copy")
+                .option("-bsf:v", format!("{}_mp4toannexb", video_codec))
+                .flag("-an")
+                .option("-f", "mpegts")
+                .build(&tail_path);
+            Self::run_ffmpeg(tail_args, options.threads, cancel, |encoded, speed| {
+                on_progress(head_duration + encoded, speed)
+            }).await?;
+            
+            let list = [&head_path, &tail_path]
+                .iter()
+                .map(|path| format!("file '{}'\n", path.replace('\'', "'\\''")))
+                .collect::<String>();
+            fs::write(&list_path, list)
+                .map_err(|e| format!("Failed to write concat list: {}", e))?;
+            
+            // Video from the joined parts, audio from the source
+            let mut join_args = FfmpegArgs::new()
+                .option("-f", "concat")
+                .option("-safe", "0")
+                .input(&list_path)
+                .seek_input(start_time)
+                .duration(end_time - start_time)
+                .input(input_path)
+                .option("-map", "0:v:0")
+                .option("-map", "1:a:0?")
+                .copy_video(&options.profile, video_codec);
+            join_args = match &options.profile.audio {
+                Some(audio) if !source.audio_tracks.is_empty() => join_args.audio_encoding(audio),
+                _ => join_args.flag("-an"),
+            };
+            // Keep the source's time base, so copied frames keep their exact timestamps
+            let timescale = source.video.as_ref()
+                .and_then(|video| video.time_base.as_deref())
+                .and_then(|time_base| time_base.strip_prefix("1/"));
+            if let (Some(timescale), true) = (timescale, options.profile.is_mp4_family()) {
+                join_args = join_args.option("-video_track_timescale", timescale);
+            }
+            let join_args = options.comment(join_args.container_flags(&options.profile)).build(output_path);
+            Self::run_ffmpeg(join_args, 0, cancel, |_, _| {}).await
+        }.await;
+        
+        for path in [&head_path, &tail_path, &list_path] {
+            let _ = fs::remove_file(path);
+        }
+        result
+    }
+    
+    /// Stream copy starting exactly on a keyframe (seeking before `-i` lands on it)
+    fn copy_args(
+        input_path: &str,
+        start_time: Timestamp,
+        end_time: Timestamp,
+        source: &MediaInfo,
+        profile: &ExportProfile,
+    ) -> FfmpegArgs {
+        FfmpegArgs::new()
+            .seek_input(start_time)
+            .input(input_path)
+            .duration(end_time - start_time)
+            .map_first_streams()
+            .copy_video(profile, source.video_codec().unwrap_or_default())
+            .copy_or_encode_audio(profile, source.audio_codec())
+            .option("-avoid_negative_ts", "make_zero") // Start the copied packets at zero
+            .container_flags(profile)
+    }
+    
+    /// Re-encode the partial GOP before the first keyframe to match the copied remainder
+    ///
+    /// Same encoder, profile, level and pixel format as the source, with the
+    /// source's time base, written as video-only MPEG-TS.
+    fn head_args(
+        input_path: &str,
+        start_time: Timestamp,
+        keyframe: Timestamp,
+        source: &MediaInfo,
+        output_path: &str,
+    ) -> Vec<String> {
+        let mut args = FfmpegArgs::new()
+            .seek_input(start_time)
+            .input(input_path)
+            .duration(keyframe - start_time)
+            .option("-map", "0:v:0")
+            .option("-c:v", source.video_codec().and_then(matching_encoder).unwrap_or("libx264"))
+            .option("-preset", "fast")
+            .option("-crf", "18"); // A few frames, so keep them close to the source
+        
+        if let Some(video) = &source.video {
+            args = args.raw(&matching_profile_and_level(video));
+            if let Some(pixel_format) = &video.pixel_format {
+                args = args.option("-pix_fmt", pixel_format.as_str());
+            }
+        }
+        args.option("-enc_time_base:v", "demux")
+            .flag("-an")
+            .option("-f", "mpegts")
+            .build(output_path)
+    }
+    
+    /// Run one FFmpeg encode, reporting output time encoded so far and encode speed
+    ///
+    /// `threads` caps FFmpeg's threads (0 = FFmpeg's default of all cores).
+    pub(crate) async fn run_ffmpeg(
+        mut args: Vec<String>,
+        threads: u32,
+        cancel: &CancelToken,
+        on_progress: impl Fn(Duration, Option<f32>),
+    ) -> Result<(), String> {
+        // Options go before the output file, which is always last
+        let output_path = args.pop().unwrap_or_default();
+        args.extend([
+            "-progress".to_string(), "pipe:1".to_string(), // Machine-readable progress on stdout
+            "-nostats".to_string(),                        // ...instead of the stderr status line
+        ]);
+        if threads > 0 {
+            // Keeps parallel workers from oversubscribing the CPU
+            args.extend(["-threads".to_string(), threads.to_string()]);
+        }
+        args.push(output_path);
         
         println!("FFmpeg args: {:?}", args);
         
-        let (mut rx, _child) = Command::new_sidecar("ffmpeg")
+        let (mut rx, child) = Command::new_sidecar("ffmpeg")
             .map_err(|e| {
                 let err_msg = format!("Failed to find FFmpeg binary: {}", e);
                 println!("ERROR: {}", err_msg);
@@ -100,15 +812,17 @@ impl FFmpegService {
         
         println!("FFmpeg process spawned successfully");
         
-        // Monitor process output
-        let mut stdout_lines = Vec::new();
+        // Monitor process output (killing FFmpeg if the job is cancelled)
+        let mut child = Some(child);
         let mut stderr_lines = Vec::new();
+        let mut progress = ProgressReader::default();
         
-        while let Some(event) = rx.recv().await {
+        while let Some(event) = cancel.next_event(&mut rx, &mut child).await? {
             match event {
                 CommandEvent::Stdout(line) => {
-                    println!("FFmpeg STDOUT: {}", line);
-                    stdout_lines.push(line.clone());
+                    if let Some((encoded, speed)) = progress.read_line(&line) {
+                        on_progress(encoded, speed);
+                    }
                 }
                 CommandEvent::Stderr(line) => {
                     println!("FFmpeg STDERR: {}", line);
@@ -147,3 +861,109 @@ impl FFmpegService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed a `-progress` block to `reader`, returning what it reports at the end
+    fn block(reader: &mut ProgressReader, out_time_us: &str, speed: &str) -> (Duration, Option<f32>) {
+        let lines = [
+            "frame=120".to_string(),
+            format!("out_time_us={}", out_time_us),
+            "out_time=00:00:04.000000".to_string(),
+            format!("speed={}", speed),
+            "progress=continue".to_string(),
+        ];
+        let mut reported = None;
+        for line in &lines {
+            if let Some(progress) = reader.read_line(line) {
+                assert!(reported.is_none(), "reported before the block ended");
+                reported = Some(progress);
+            }
+        }
+        reported.unwrap()
+    }
+
+    #[test]
+    fn reads_progress_blocks() {
+        let mut reader = ProgressReader::default();
+
+        // The first block of an encode has no time or speed yet
+        assert_eq!(block(&mut reader, "N/A", "N/A"), (Duration::ZERO, None));
+        assert_eq!(block(&mut reader, "-23220", "N/A"), (Duration::ZERO, None));
+        assert_eq!(block(&mut reader, "4000000", " 2.5x"), (Duration::from_secs(4), Some(2.5)));
+        assert_eq!(reader.read_line("out_time_ms=6500000"), None);
+        assert_eq!(reader.read_line("progress=end"), Some((Duration::from_millis(6500), Some(2.5))));
+    }
+
+    #[test]
+    fn tracks_one_encode() {
+        let mut tracker = ProgressTracker::new(vec![Duration::from_secs(20)]);
+        let mut reader = ProgressReader::default();
+
+        let (encoded, speed) = block(&mut reader, "5000000", "2x");
+        let event = tracker.update(0, encoded, speed);
+        assert_eq!((event.current, event.total, event.active, event.clip_index), (0, 1, 1, 0));
+        assert_eq!(event.clip_percent, 25.0);
+        assert_eq!(event.overall_percent, 25.0);
+        assert_eq!(event.speed, Some(2.0));
+        // 15s left at 2x
+        assert_eq!(event.eta_seconds, Some(8));
+
+        // Output time can run past the clip's length (e.g. audio padding)
+        let (encoded, speed) = block(&mut reader, "21000000", "2x");
+        assert_eq!(tracker.update(0, encoded, speed).clip_percent, 100.0);
+
+        let event = tracker.finish(0);
+        assert_eq!((event.current, event.active), (1, 0));
+        assert_eq!(event.overall_percent, 100.0);
+        assert_eq!(event.eta_seconds, Some(0));
+    }
+
+    #[test]
+    fn combines_concurrent_encodes() {
+        let mut tracker = ProgressTracker::new(vec![Duration::from_secs(30), Duration::from_secs(10), Duration::from_secs(60)]);
+        let (mut first, mut second) = (ProgressReader::default(), ProgressReader::default());
+
+        let (encoded, speed) = block(&mut first, "6000000", "3x");
+        let event = tracker.update(0, encoded, speed);
+        assert_eq!(event.overall_percent, 6.0);
+        assert_eq!(event.speed, Some(3.0));
+
+        // Speeds of workers running side by side add up
+        let (encoded, speed) = block(&mut second, "4000000", "1x");
+        let event = tracker.update(1, encoded, speed);
+        assert_eq!((event.active, event.clip_index), (2, 1));
+        assert_eq!(event.clip_percent, 40.0);
+        assert_eq!(event.overall_percent, 10.0);
+        assert_eq!(event.speed, Some(4.0));
+        // 90s of 100s left at 4x
+        assert_eq!(event.eta_seconds, Some(23));
+
+        // A block without a speed keeps the worker's last one
+        let event = tracker.update(0, Duration::from_secs(12), None);
+        assert_eq!(event.speed, Some(4.0));
+
+        // Once the second clip is done only the first one's speed counts
+        let event = tracker.finish(1);
+        assert_eq!((event.current, event.active), (1, 1));
+        assert_eq!(event.overall_percent, 22.0);
+        assert_eq!(event.speed, Some(3.0));
+        assert_eq!(event.eta_seconds, Some(26));
+    }
+
+    #[test]
+    fn keeps_the_last_speed_between_clips() {
+        let mut tracker = ProgressTracker::new(vec![Duration::from_secs(10), Duration::from_secs(10)]);
+        tracker.update(0, Duration::from_secs(5), Some(5.0));
+
+        // Nothing is encoding right after a clip finishes, but the ETA shouldn't vanish
+        let event = tracker.finish(0);
+        assert_eq!(event.speed, Some(5.0));
+        assert_eq!(event.eta_seconds, Some(2));
+
+        let empty = ProgressTracker::new(vec![Duration::ZERO]).update(0, Duration::ZERO, None);
+        assert_eq!((empty.clip_percent, empty.overall_percent, empty.eta_seconds), (100.0, 100.0, None));
+    }
+}
@@ -0,0 +1,414 @@
+use crate::models::{AudioEncoding, ExportProfile, Reframe, ReframeFit, Timestamp, VideoEncoding};
+use std::time::Duration;
+
+/// Builds an FFmpeg argument list
+///
+/// Options apply to the next input or, after the last `-i`, to the output,
+/// so methods are called in command-line order and `build` adds the output.
+/// Video and audio filters are collected and written as single `-vf` and
+/// `-af` chains.
+#[derive(Debug, Default, Clone)]
+pub struct FfmpegArgs {
+    args: Vec<String>,
+    video_filters: Vec<String>,
+    audio_filters: Vec<String>,
+}
+
+impl FfmpegArgs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `-key value`
+    pub fn option(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.args.push(key.to_string());
+        self.args.push(value.into());
+        self
+    }
+
+    /// `-key` with no value
+    pub fn flag(mut self, key: &str) -> Self {
+        self.args.push(key.to_string());
+        self
+    }
+
+    /// Add arguments verbatim
+    pub fn raw<S: AsRef<str>>(mut self, args: &[S]) -> Self {
+        self.args.extend(args.iter().map(|arg| arg.as_ref().to_string()));
+        self
+    }
+
+    pub fn input(self, path: &str) -> Self {
+        self.option("-i", path)
+    }
+
+    /// Seek before opening the input: fast, and exact on a keyframe when copying
+    pub fn seek_input(self, start: Timestamp) -> Self {
+        self.option("-ss", start.to_ffmpeg())
+    }
+
+    /// Trim the output to `start..end` of the input (frame-accurate, decodes from the top)
+    pub fn trim_output(self, start: Timestamp, end: Timestamp) -> Self {
+        self.option("-ss", start.to_ffmpeg()).option("-to", end.to_ffmpeg())
+    }
+
+    /// Stop after `duration` of output
+    pub fn duration(self, duration: Duration) -> Self {
+        self.option("-t", format!("{:.3}", duration.as_secs_f64()))
+    }
+
+    /// Only the first video stream and the first audio stream, if there is one
+    pub fn map_first_streams(self) -> Self {
+        self.option("-map", "0:v:0").option("-map", "0:a:0?")
+    }
+
+    pub fn video_encoding(mut self, video: &VideoEncoding) -> Self {
+        self = self.option("-c:v", video.codec.as_str());
+        if let Some(preset) = &video.preset {
+            self = self.option("-preset", preset.as_str());
+        }
+        if let Some(crf) = video.crf {
+            self = self.option("-crf", crf.to_string());
+        }
+        if let Some(bitrate) = &video.bitrate {
+            self = self.option("-b:v", bitrate.as_str());
+        }
+        if let Some(profile) = &video.profile {
+            self = self.option("-profile:v", profile.as_str());
+        }
+        if let Some(pixel_format) = &video.pixel_format {
+            self = self.option("-pix_fmt", pixel_format.as_str());
+        }
+        self.raw(&video.extra_args)
+    }
+
+    pub fn audio_encoding(mut self, audio: &AudioEncoding) -> Self {
+        self = self.option("-c:a", audio.codec.as_str());
+        if let Some(bitrate) = &audio.bitrate {
+            self = self.option("-b:a", bitrate.as_str());
+        }
+        if let Some(sample_rate) = audio.sample_rate {
+            self = self.option("-ar", sample_rate.to_string());
+        }
+        if let Some(channels) = audio.channels {
+            self = self.option("-ac", channels.to_string());
+        }
+        self
+    }
+
+    /// Encode with a profile's codecs, dropping streams it has no settings for
+    pub fn profile(self, profile: &ExportProfile) -> Self {
+        let args = match &profile.video {
+            Some(video) => self.video_encoding(video),
+            None => self.flag("-vn"),
+        };
+        let args = match &profile.audio {
+            Some(audio) => args.audio_encoding(audio),
+            None => args.flag("-an"),
+        };
+        args.container_flags(profile)
+    }
+
+    /// Stream-copy the video, tagged the way the profile's container wants
+    pub fn copy_video(self, profile: &ExportProfile, source_codec: &str) -> Self {
+        let args = self.option("-c:v", "copy");
+        match (source_codec, profile.is_mp4_family()) {
+            // hvc1 is the tag QuickTime and iOS expect
+            ("hevc", true) => args.option("-tag:v", "hvc1"),
+            _ => args,
+        }
+    }
+
+    /// Copy the audio when the container can hold it, otherwise use the profile's encoder
+    pub fn copy_or_encode_audio(self, profile: &ExportProfile, source_codec: Option<&str>) -> Self {
+        match (source_codec, &profile.audio) {
+            (Some(codec), Some(_)) if profile.can_hold_audio(codec) => self.option("-c:a", "copy"),
+            (Some(_), Some(audio)) => self.audio_encoding(audio),
+            _ => self.flag("-an"),
+        }
+    }
+
+    /// Append to the output's video filter chain
+    pub fn video_filter(mut self, filter: impl Into<String>) -> Self {
+        self.video_filters.push(filter.into());
+        self
+    }
+
+    /// Append to the output's audio filter chain
+    pub fn audio_filter(mut self, filter: impl Into<String>) -> Self {
+        self.audio_filters.push(filter.into());
+        self
+    }
+
+    /// Fade in from black and silence, and out again at the end of `length`
+    ///
+    /// Only touches the streams the profile keeps. Timestamps must start at
+    /// zero, so seek the input rather than trimming the output.
+    pub fn fades(mut self, fade_in: Duration, fade_out: Duration, length: Duration, profile: &ExportProfile) -> Self {
+        let mut fades = Vec::new();
+        if !fade_in.is_zero() {
+            fades.push(format!("t=in:st=0:d={:.3}", fade_in.as_secs_f64()));
+        }
+        if !fade_out.is_zero() {
+            fades.push(format!(
+                "t=out:st={:.3}:d={:.3}",
+                length.saturating_sub(fade_out).as_secs_f64(),
+                fade_out.as_secs_f64()
+            ));
+        }
+        for fade in fades {
+            if profile.video.is_some() {
+                self = self.video_filter(format!("fade={}", fade));
+            }
+            if profile.audio.is_some() {
+                self = self.audio_filter(format!("afade={}", fade));
+            }
+        }
+        self
+    }
+
+    /// `-metadata key=value` on the output
+    pub fn metadata(self, key: &str, value: &str) -> Self {
+        self.option("-metadata", format!("{}={}", key, value))
+    }
+
+    /// Crop, scale or letterbox the video into a new aspect ratio
+    pub fn reframe(self, reframe: &Reframe) -> Self {
+        let (width, height) = reframe.aspect.output_size();
+        let (ratio_w, ratio_h) = reframe.aspect.ratio();
+        // Largest region of the source with the target shape
+        let crop_size = format!(
+            "w='min(iw,ih*{ratio_w}/{ratio_h})':h='min(ih,iw*{ratio_h}/{ratio_w})'"
+        );
+        // Scale to cover the frame, then trim the overflow
+        let fill = format!("scale={width}:{height}:force_original_aspect_ratio=increase,crop={width}:{height}");
+
+        let filter = match &reframe.fit {
+            ReframeFit::Center => format!("crop={crop_size},scale={width}:{height}"),
+            ReframeFit::Offset { position } => format!(
+                "crop={crop_size}:x='(iw-ow)*{:.4}':y='(ih-oh)/2',scale={width}:{height}",
+                // `clamp` keeps NaN, which FFmpeg can't evaluate
+                if position.is_nan() { 0.5 } else { position.clamp(0.0, 1.0) }
+            ),
+            ReframeFit::Crop { x, y, width: crop_width, height: crop_height } => {
+                format!("crop={crop_width}:{crop_height}:{x}:{y},{fill}")
+            }
+            ReframeFit::BlurredBackground => format!(
+                "split=2[back][front];\
+                 [back]{fill},boxblur=20:2[blurred];\
+                 [front]scale={width}:{height}:force_original_aspect_ratio=decrease:force_divisible_by=2[fitted];\
+                 [blurred][fitted]overlay=(W-w)/2:(H-h)/2"
+            ),
+        };
+        self.video_filter(filter).video_filter("setsar=1")
+    }
+
+    /// Burn in a subtitle file (rendered with libass)
+    pub fn subtitles(self, path: &str) -> Self {
+        self.video_filter(Self::subtitles_filter(path))
+    }
+
+    /// `subtitles` filter for a file, for use in a `-vf` or `-filter_complex` graph
+    pub fn subtitles_filter(path: &str) -> String {
+        // Escaped once for the filter's option parser, then again for the
+        // filtergraph; forward slashes keep Windows paths out of it
+        let escape = |value: &str, special: &[char]| {
+            value.chars().fold(String::new(), |mut escaped, c| {
+                if special.contains(&c) {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+                escaped
+            })
+        };
+        let path = path.replace('\\', "/");
+        let option = escape(&path, &['\\', '\'', ':']);
+        format!("subtitles={}", escape(&option, &['\\', '\'', '[', ']', ',', ';']))
+    }
+
+    /// Output flags implied by the container
+    pub fn container_flags(self, profile: &ExportProfile) -> Self {
+        if profile.is_mp4_family() {
+            self.option("-movflags", "+faststart") // Enable streaming
+        } else {
+            self
+        }
+    }
+
+    /// Finish with the output file, overwriting it if it exists
+    pub fn build(self, output_path: &str) -> Vec<String> {
+        let video_filters = self.video_filters.join(",");
+        let audio_filters = self.audio_filters.join(",");
+        let mut args = self;
+        if !video_filters.is_empty() {
+            args = args.option("-vf", video_filters);
+        }
+        if !audio_filters.is_empty() {
+            args = args.option("-af", audio_filters);
+        }
+        args.flag("-y").raw(&[output_path]).args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AspectRatio;
+
+    fn profile(id: &str) -> ExportProfile {
+        ExportProfile::builtin().into_iter().find(|profile| profile.id == id).unwrap()
+    }
+
+    fn encode(profile: &ExportProfile) -> Vec<String> {
+        FfmpegArgs::new().input("in.mov").profile(profile).build(&format!("out.{}", profile.extension))
+    }
+
+    #[test]
+    fn encodes_with_each_builtin_profile() {
+        let expected: &[(&str, &[&str])] = &[
+            ("h264", &["-c:v", "libx264", "-preset", "fast", "-crf", "23", "-c:a", "aac", "-b:a", "192k", "-movflags", "+faststart", "-y", "out.mp4"]),
+            ("h264_hq", &["-c:v", "libx264", "-preset", "slow", "-crf", "18", "-pix_fmt", "yuv420p", "-c:a", "aac", "-b:a", "256k", "-movflags", "+faststart", "-y", "out.mp4"]),
+            ("hevc", &["-c:v", "libx265", "-preset", "medium", "-crf", "24", "-tag:v", "hvc1", "-c:a", "aac", "-b:a", "192k", "-movflags", "+faststart", "-y", "out.mp4"]),
+            ("vp9_webm", &["-c:v", "libvpx-vp9", "-crf", "32", "-b:v", "0", "-row-mt", "1", "-c:a", "libopus", "-b:a", "128k", "-y", "out.webm"]),
+            ("prores_proxy", &["-c:v", "prores_ks", "-profile:v", "0", "-pix_fmt", "yuv422p10le", "-c:a", "pcm_s16le", "-movflags", "+faststart", "-y", "out.mov"]),
+            ("mp3", &["-vn", "-c:a", "libmp3lame", "-b:a", "192k", "-y", "out.mp3"]),
+            ("m4a", &["-vn", "-c:a", "aac", "-b:a", "192k", "-movflags", "+faststart", "-y", "out.m4a"]),
+            ("wav", &["-vn", "-c:a", "pcm_s16le", "-y", "out.wav"]),
+        ];
+
+        assert_eq!(expected.len(), ExportProfile::builtin().len());
+        for (id, args) in expected {
+            let mut full = vec!["-i", "in.mov"];
+            full.extend_from_slice(args);
+            assert_eq!(encode(&profile(id)), full, "profile {}", id);
+        }
+    }
+
+    #[test]
+    fn drops_audio_for_profiles_without_it() {
+        let silent = ExportProfile { audio: None, ..profile("h264") };
+        let args = encode(&silent);
+
+        assert!(args.contains(&"-an".to_string()));
+        assert!(!args.contains(&"-c:a".to_string()));
+    }
+
+    #[test]
+    fn copies_hevc_with_the_hvc1_tag_only_into_mp4() {
+        let mp4 = FfmpegArgs::new().copy_video(&profile("hevc"), "hevc").build("out.mp4");
+        assert_eq!(mp4, ["-c:v", "copy", "-tag:v", "hvc1", "-y", "out.mp4"]);
+
+        let webm = FfmpegArgs::new().copy_video(&profile("vp9_webm"), "vp9").build("out.webm");
+        assert_eq!(webm, ["-c:v", "copy", "-y", "out.webm"]);
+    }
+
+    #[test]
+    fn copies_audio_the_container_can_hold() {
+        let h264 = profile("h264");
+        let copied = FfmpegArgs::new().copy_or_encode_audio(&h264, Some("aac")).build("out.mp4");
+        assert_eq!(copied, ["-c:a", "copy", "-y", "out.mp4"]);
+
+        let encoded = FfmpegArgs::new().copy_or_encode_audio(&h264, Some("opus")).build("out.mp4");
+        assert_eq!(encoded, ["-c:a", "aac", "-b:a", "192k", "-y", "out.mp4"]);
+
+        let none = FfmpegArgs::new().copy_or_encode_audio(&h264, None).build("out.mp4");
+        assert_eq!(none, ["-an", "-y", "out.mp4"]);
+    }
+
+    #[test]
+    fn joins_filters_into_single_chains_before_the_output() {
+        let args = FfmpegArgs::new()
+            .input("in.mov")
+            .video_filter("scale=1280:720")
+            .fades(Duration::from_secs(1), Duration::from_millis(500), Duration::from_secs(10), &profile("h264"))
+            .audio_filter("volume=2")
+            .metadata("comment", "take 2")
+            .build("out.mp4");
+
+        assert_eq!(
+            args,
+            [
+                "-i", "in.mov",
+                "-metadata", "comment=take 2",
+                "-vf", "scale=1280:720,fade=t=in:st=0:d=1.000,fade=t=out:st=9.500:d=0.500",
+                "-af", "afade=t=in:st=0:d=1.000,afade=t=out:st=9.500:d=0.500,volume=2",
+                "-y", "out.mp4",
+            ]
+        );
+    }
+
+    #[test]
+    fn fades_only_the_streams_the_profile_keeps() {
+        let args = FfmpegArgs::new()
+            .fades(Duration::from_secs(1), Duration::ZERO, Duration::from_secs(5), &profile("mp3"))
+            .build("out.mp3");
+
+        assert_eq!(args, ["-af", "afade=t=in:st=0:d=1.000", "-y", "out.mp3"]);
+    }
+
+    fn reframe(aspect: AspectRatio, fit: ReframeFit) -> String {
+        let args = FfmpegArgs::new().reframe(&Reframe { aspect, fit }).build("out.mp4");
+        assert_eq!(args[0], "-vf");
+        args[1].clone()
+    }
+
+    #[test]
+    fn reframes_to_the_center() {
+        assert_eq!(
+            reframe(AspectRatio::Vertical, ReframeFit::Center),
+            "crop=w='min(iw,ih*9/16)':h='min(ih,iw*16/9)',scale=1080:1920,setsar=1"
+        );
+        assert_eq!(
+            reframe(AspectRatio::Square, ReframeFit::Center),
+            "crop=w='min(iw,ih*1/1)':h='min(ih,iw*1/1)',scale=1080:1080,setsar=1"
+        );
+    }
+
+    #[test]
+    fn reframes_at_an_offset() {
+        let offset = |position| reframe(AspectRatio::Vertical, ReframeFit::Offset { position });
+
+        assert_eq!(
+            offset(0.3),
+            "crop=w='min(iw,ih*9/16)':h='min(ih,iw*16/9)':x='(iw-ow)*0.3000':y='(ih-oh)/2',scale=1080:1920,setsar=1"
+        );
+        assert!(offset(-0.5).contains(":x='(iw-ow)*0.0000':"));
+        assert!(offset(1.5).contains(":x='(iw-ow)*1.0000':"));
+        assert!(offset(f64::INFINITY).contains(":x='(iw-ow)*1.0000':"));
+        assert!(offset(f64::NAN).contains(":x='(iw-ow)*0.5000':"));
+    }
+
+    #[test]
+    fn reframes_a_crop_rectangle() {
+        assert_eq!(
+            reframe(AspectRatio::Vertical, ReframeFit::Crop { x: 100, y: 40, width: 540, height: 960 }),
+            "crop=540:960:100:40,\
+             scale=1080:1920:force_original_aspect_ratio=increase,crop=1080:1920,setsar=1"
+        );
+    }
+
+    #[test]
+    fn reframes_over_a_blurred_background() {
+        assert_eq!(
+            reframe(AspectRatio::Square, ReframeFit::BlurredBackground),
+            "split=2[back][front];\
+             [back]scale=1080:1080:force_original_aspect_ratio=increase,crop=1080:1080,boxblur=20:2[blurred];\
+             [front]scale=1080:1080:force_original_aspect_ratio=decrease:force_divisible_by=2[fitted];\
+             [blurred][fitted]overlay=(W-w)/2:(H-h)/2,setsar=1"
+        );
+    }
+
+    #[test]
+    fn escapes_subtitle_paths() {
+        assert_eq!(FfmpegArgs::subtitles_filter("/tmp/clip.ass"), "subtitles=/tmp/clip.ass");
+        // Backslashes become forward slashes and the drive colon is escaped twice
+        assert_eq!(
+            FfmpegArgs::subtitles_filter(r"C:\Users\me\Clips\clip.ass"),
+            r"subtitles=C\\:/Users/me/Clips/clip.ass"
+        );
+        assert_eq!(
+            FfmpegArgs::subtitles_filter("/tmp/Dana's [final], take 2.ass"),
+            r"subtitles=/tmp/Dana\\\'s \[final\]\, take 2.ass"
+        );
+    }
+}
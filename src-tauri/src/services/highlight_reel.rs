@@ -0,0 +1,442 @@
+use crate::models::{
+    CaptionSettings, ClipProgress, ExportSettings, HighlightReelOptions, HighlightReelResult, ReelTransition, Timestamp,
+    ValidatedClip,
+};
+use crate::services::captions::CaptionWriter;
+use crate::services::ffmpeg::ProgressTracker;
+use crate::services::{CancelToken, FFmpegService, FfmpegArgs, MediaInfoService};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Audio is mixed at this rate and layout so every segment matches
+const SAMPLE_RATE: u32 = 48_000;
+
+/// Fade on the audio at hard cuts, just long enough to avoid clicks
+const CUT_AUDIO_FADE: f64 = 0.02;
+
+/// Transitions shorter than this are rendered as cuts
+const MIN_TRANSITION: f64 = 0.05;
+
+/// One piece of the reel, in playback order
+enum ReelItem {
+    /// Title card, drawn from an ASS file
+    Card { subtitles_path: String, seconds: f64 },
+    /// Part of the source video, read from FFmpeg input `input`
+    Segment { input: usize, seconds: f64 },
+}
+
+impl ReelItem {
+    fn seconds(&self) -> f64 {
+        match self {
+            ReelItem::Card { seconds, .. } | ReelItem::Segment { seconds, .. } => *seconds,
+        }
+    }
+}
+
+/// Output frame and frame rate every item is conformed to
+struct ReelFormat {
+    width: u32,
+    height: u32,
+    frame_rate: f64,
+    /// Whether the source has audio to take from the segments
+    source_audio: bool,
+}
+
+/// Renders clips into one compilation video
+pub struct HighlightReelService;
+
+impl HighlightReelService {
+    /// Render `clips`, in the given order, into `[VideoName]_Highlights` in the clips folder
+    ///
+    /// Everything happens in one FFmpeg filter graph: each clip is read with its
+    /// own seeked input, conformed to the source's frame size and rate, and
+    /// joined to the next with a cut (`concat`) or a transition (`xfade` and
+    /// `acrossfade`). Title cards are black frames with the clip title rendered
+    /// by libass.
+    pub async fn render<F>(
+        video_path: &str,
+        clips: &[ValidatedClip],
+        options: &HighlightReelOptions,
+        export: &ExportSettings,
+        caption_style: &CaptionSettings,
+        cancel: &CancelToken,
+        progress_callback: F,
+    ) -> Result<HighlightReelResult, String>
+    where
+        F: Fn(ClipProgress),
+    {
+        if clips.is_empty() {
+            return Err("No clips selected".to_string());
+        }
+
+        let profile = export.profile(options.profile_id.as_deref());
+        if profile.is_audio_only() {
+            return Err(format!("{} is audio only; pick a video profile for the highlight reel", profile.name));
+        }
+
+        let info = MediaInfoService::probe(video_path).await?;
+        let video = info.video.as_ref().ok_or("The video has no video stream")?;
+        let format = ReelFormat {
+            // Most encoders need even dimensions
+            width: (video.width.max(2) / 2) * 2,
+            height: (video.height.max(2) / 2) * 2,
+            frame_rate: video.frame_rate.unwrap_or(30.0),
+            source_audio: !info.audio_tracks.is_empty(),
+        };
+
+        let video_path_obj = Path::new(video_path);
+        let video_name = video_path_obj
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or("Invalid video filename")?;
+        let output_dir = video_path_obj
+            .parent()
+            .ok_or("Cannot determine output directory")?
+            .join(format!("{}_Clips", video_name));
+        fs::create_dir_all(&output_dir)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        let output_path = output_dir.join(format!("{}_Highlights.{}", video_name, profile.extension));
+        let output_str = output_path.to_str().ok_or("Invalid output path")?.to_string();
+
+        println!("=== Rendering Highlight Reel ===");
+        println!("Clips: {}", clips.len());
+        println!("Transition: {:?}, title cards: {}", options.transition, options.title_cards);
+        println!("Output: {}", output_str);
+
+        // Title cards first, so a failure leaves nothing half-made
+        let mut items = Vec::new();
+        let mut args = FfmpegArgs::new();
+        let mut card_paths = Vec::new();
+        for (index, clip) in clips.iter().enumerate() {
+            if options.title_cards {
+                let seconds = options.title_card_seconds();
+                let subtitles_path = format!("{}.card{}.ass", output_str, index + 1);
+                let card = CaptionWriter::title_card(
+                    &clip.title,
+                    caption_style,
+                    (format.width, format.height),
+                    Duration::from_secs_f64(seconds),
+                );
+                card_paths.push(subtitles_path.clone());
+                if let Err(e) = fs::write(&subtitles_path, card) {
+                    Self::remove_files(&card_paths);
+                    return Err(format!("Failed to write title card: {}", e));
+                }
+                items.push(ReelItem::Card { subtitles_path, seconds });
+            }
+
+            let duration = clip.end_time - clip.start_time;
+            args = args.seek_input(clip.start_time).duration(duration).input(video_path);
+            items.push(ReelItem::Segment { input: index, seconds: duration.as_secs_f64() });
+        }
+
+        // Each transition overlaps two items, so it has to fit inside the shortest one
+        let shortest = items.iter().map(ReelItem::seconds).fold(f64::MAX, f64::min);
+        let transition_seconds = match options.transition {
+            ReelTransition::Cut => 0.0,
+            _ => options.transition_seconds.min(shortest / 3.0),
+        };
+        let transition_seconds = if transition_seconds < MIN_TRANSITION { 0.0 } else { transition_seconds };
+        let include_audio = profile.audio.is_some();
+
+        let graph = Self::filter_graph(&items, &format, options, transition_seconds, include_audio);
+        println!("Filter graph: {}", graph);
+
+        args = args.option("-filter_complex", graph).option("-map", "[vout]");
+        if include_audio {
+            args = args.option("-map", "[aout]");
+        }
+        let args = args.profile(&profile).build(&output_str);
+
+        let total_seconds = items.iter().map(ReelItem::seconds).sum::<f64>()
+            - transition_seconds * (items.len() - 1) as f64;
+        let total = Duration::from_secs_f64(total_seconds.max(0.0));
+        let tracker = Mutex::new(ProgressTracker::new(vec![total]));
+        let result = FFmpegService::run_ffmpeg(args, export.threads_per_clip, cancel, |encoded, speed| {
+            let event = tracker.lock().unwrap().update(0, encoded, speed);
+            progress_callback(event);
+        })
+        .await;
+
+        Self::remove_files(&card_paths);
+        if let Err(e) = result {
+            let _ = fs::remove_file(&output_path);
+            return Err(e);
+        }
+        progress_callback(tracker.lock().unwrap().finish(0));
+
+        println!("✅ Highlight reel written: {}", output_str);
+        Ok(HighlightReelResult {
+            output_path: output_str,
+            output_directory: output_dir.to_string_lossy().to_string(),
+            duration: Timestamp::from_millis(total.as_millis() as u64),
+        })
+    }
+
+    /// Filter graph producing `[vout]` (and `[aout]` with `include_audio`) from the items
+    fn filter_graph(
+        items: &[ReelItem],
+        format: &ReelFormat,
+        options: &HighlightReelOptions,
+        transition_seconds: f64,
+        include_audio: bool,
+    ) -> String {
+        let ReelFormat { width, height, frame_rate, .. } = *format;
+        let last = items.len() - 1;
+        let mut chains: Vec<String> = Vec::new();
+
+        // Conform every item to the same frame size, rate and pixel format
+        for (index, item) in items.iter().enumerate() {
+            let video = match item {
+                ReelItem::Card { subtitles_path, seconds } => format!(
+                    "color=c=black:s={width}x{height}:r={frame_rate:.3}:d={seconds:.3},format=yuv420p,{}",
+                    FfmpegArgs::subtitles_filter(subtitles_path)
+                ),
+                ReelItem::Segment { input, .. } => format!(
+                    "[{input}:v:0]scale={width}:{height}:force_original_aspect_ratio=decrease,\
+                     pad={width}:{height}:(ow-iw)/2:(oh-ih)/2,fps={frame_rate:.3},format=yuv420p,setpts=PTS-STARTPTS"
+                ),
+            };
+            chains.push(format!("{video},setsar=1[v{index}]"));
+
+            if !include_audio {
+                continue;
+            }
+            let seconds = item.seconds();
+            let mut audio = match item {
+                ReelItem::Segment { input, .. } if format.source_audio => format!("[{input}:a:0]"),
+                _ => format!("anullsrc=r={SAMPLE_RATE}:cl=stereo:d={seconds:.3},"),
+            };
+            // Pad or trim to the exact item length so audio and video stay in step
+            let _ = write!(
+                audio,
+                "aresample={SAMPLE_RATE},aformat=sample_fmts=fltp:channel_layouts=stereo,\
+                 asetpts=PTS-STARTPTS,apad,atrim=end={seconds:.3}"
+            );
+
+            if transition_seconds == 0.0 {
+                let _ = write!(
+                    audio,
+                    ",afade=t=in:d={CUT_AUDIO_FADE},afade=t=out:st={:.3}:d={CUT_AUDIO_FADE}",
+                    (seconds - CUT_AUDIO_FADE).max(0.0)
+                );
+            } else if !options.audio_crossfade {
+                // Without a crossfade the audio dips instead: half the overlap is cut
+                // from each side of a join and faded, so the total length still matches
+                let half = transition_seconds / 2.0;
+                let start = if index > 0 { half } else { 0.0 };
+                let end = if index < last { seconds - half } else { seconds };
+                let _ = write!(audio, ",atrim=start={start:.3}:end={end:.3},asetpts=PTS-STARTPTS");
+                if index > 0 {
+                    let _ = write!(audio, ",afade=t=in:d={half:.3}");
+                }
+                if index < last {
+                    let _ = write!(audio, ",afade=t=out:st={:.3}:d={half:.3}", end - start - half);
+                }
+            }
+            chains.push(format!("{audio}[a{index}]"));
+        }
+
+        // Join the items
+        if transition_seconds == 0.0 || items.len() == 1 {
+            let video_inputs: String = (0..items.len()).map(|i| format!("[v{i}]")).collect();
+            chains.push(format!("{video_inputs}concat=n={}:v=1:a=0[vout]", items.len()));
+        } else {
+            let transition = match options.transition {
+                ReelTransition::DipToBlack => "fadeblack",
+                _ => "fade",
+            };
+            let mut previous = "v0".to_string();
+            let mut offset = 0.0;
+            for index in 1..items.len() {
+                offset += items[index - 1].seconds() - transition_seconds;
+                let output = if index == last { "vout".to_string() } else { format!("vx{index}") };
+                chains.push(format!(
+                    "[{previous}][v{index}]xfade=transition={transition}:duration={transition_seconds:.3}:offset={offset:.3}[{output}]"
+                ));
+                previous = output;
+            }
+        }
+
+        if include_audio {
+            if transition_seconds > 0.0 && options.audio_crossfade && items.len() > 1 {
+                let mut previous = "a0".to_string();
+                for index in 1..items.len() {
+                    let output = if index == last { "aout".to_string() } else { format!("ax{index}") };
+                    chains.push(format!(
+                        "[{previous}][a{index}]acrossfade=d={transition_seconds:.3}:c1=tri:c2=tri[{output}]"
+                    ));
+                    previous = output;
+                }
+            } else {
+                let audio_inputs: String = (0..items.len()).map(|i| format!("[a{i}]")).collect();
+                chains.push(format!("{audio_inputs}concat=n={}:v=0:a=1[aout]", items.len()));
+            }
+        }
+
+        chains.join(";")
+    }
+
+    fn remove_files(paths: &[String]) {
+        for path in paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: ReelFormat = ReelFormat { width: 1280, height: 720, frame_rate: 30.0, source_audio: true };
+
+    /// Conformed video of segment `input`, labelled `[v{index}]`
+    fn segment_video(input: usize, index: usize) -> String {
+        format!(
+            "[{input}:v:0]scale=1280:720:force_original_aspect_ratio=decrease,pad=1280:720:(ow-iw)/2:(oh-ih)/2,\
+             fps=30.000,format=yuv420p,setpts=PTS-STARTPTS,setsar=1[v{index}]"
+        )
+    }
+
+    /// Source audio of segment `input` (or silence), padded or trimmed to `seconds`
+    fn audio(source: &str, seconds: &str) -> String {
+        format!(
+            "{source}aresample=48000,aformat=sample_fmts=fltp:channel_layouts=stereo,\
+             asetpts=PTS-STARTPTS,apad,atrim=end={seconds}"
+        )
+    }
+
+    fn segments(seconds: &[f64]) -> Vec<ReelItem> {
+        seconds.iter().enumerate().map(|(input, seconds)| ReelItem::Segment { input, seconds: *seconds }).collect()
+    }
+
+    fn graph(items: &[ReelItem], format: &ReelFormat, options: &HighlightReelOptions, transition_seconds: f64) -> Vec<String> {
+        HighlightReelService::filter_graph(items, format, options, transition_seconds, true)
+            .split(';')
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn joins_cuts_with_concat() {
+        let chains = graph(&segments(&[4.0, 6.0]), &FORMAT, &HighlightReelOptions::default(), 0.0);
+
+        // A short fade at each cut keeps the audio from clicking
+        assert_eq!(
+            chains,
+            [
+                segment_video(0, 0),
+                format!("{},afade=t=in:d=0.02,afade=t=out:st=3.980:d=0.02[a0]", audio("[0:a:0]", "4.000")),
+                segment_video(1, 1),
+                format!("{},afade=t=in:d=0.02,afade=t=out:st=5.980:d=0.02[a1]", audio("[1:a:0]", "6.000")),
+                "[v0][v1]concat=n=2:v=1:a=0[vout]".to_string(),
+                "[a0][a1]concat=n=2:v=0:a=1[aout]".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn crossfades_video_and_audio() {
+        let options = HighlightReelOptions { transition: ReelTransition::Crossfade, ..Default::default() };
+        let chains = graph(&segments(&[4.0, 6.0, 5.0]), &FORMAT, &options, 1.0);
+
+        // Each transition starts a second before the end of the reel so far
+        assert_eq!(
+            chains[6..],
+            [
+                "[v0][v1]xfade=transition=fade:duration=1.000:offset=3.000[vx1]",
+                "[vx1][v2]xfade=transition=fade:duration=1.000:offset=8.000[vout]",
+                "[a0][a1]acrossfade=d=1.000:c1=tri:c2=tri[ax1]",
+                "[ax1][a2]acrossfade=d=1.000:c1=tri:c2=tri[aout]",
+            ]
+        );
+        // The crossfade takes care of the joins, so the audio is left whole
+        assert_eq!(chains[3], format!("{}[a1]", audio("[1:a:0]", "6.000")));
+    }
+
+    #[test]
+    fn dips_audio_without_a_crossfade() {
+        let options = HighlightReelOptions {
+            transition: ReelTransition::DipToBlack,
+            audio_crossfade: false,
+            ..Default::default()
+        };
+        let chains = graph(&segments(&[4.0, 6.0, 5.0]), &FORMAT, &options, 1.0);
+
+        // Half a second is trimmed from each side of a join, 13s in all like the video
+        assert_eq!(
+            [&chains[1], &chains[3], &chains[5]],
+            [
+                &format!(
+                    "{},atrim=start=0.000:end=3.500,asetpts=PTS-STARTPTS,afade=t=out:st=3.000:d=0.500[a0]",
+                    audio("[0:a:0]", "4.000")
+                ),
+                &format!(
+                    "{},atrim=start=0.500:end=5.500,asetpts=PTS-STARTPTS,afade=t=in:d=0.500,afade=t=out:st=4.500:d=0.500[a1]",
+                    audio("[1:a:0]", "6.000")
+                ),
+                &format!(
+                    "{},atrim=start=0.500:end=5.000,asetpts=PTS-STARTPTS,afade=t=in:d=0.500[a2]",
+                    audio("[2:a:0]", "5.000")
+                ),
+            ]
+        );
+        assert_eq!(
+            chains[6..],
+            [
+                "[v0][v1]xfade=transition=fadeblack:duration=1.000:offset=3.000[vx1]",
+                "[vx1][v2]xfade=transition=fadeblack:duration=1.000:offset=8.000[vout]",
+                "[a0][a1][a2]concat=n=3:v=0:a=1[aout]",
+            ]
+        );
+    }
+
+    #[test]
+    fn draws_title_cards_over_black_with_silence() {
+        let items = [
+            ReelItem::Card { subtitles_path: "/tmp/reel.mp4.card1.ass".to_string(), seconds: 2.0 },
+            ReelItem::Segment { input: 0, seconds: 4.0 },
+        ];
+        let chains = graph(&items, &FORMAT, &HighlightReelOptions::default(), 0.0);
+
+        assert_eq!(
+            chains[..3],
+            [
+                "color=c=black:s=1280x720:r=30.000:d=2.000,format=yuv420p,subtitles=/tmp/reel.mp4.card1.ass,setsar=1[v0]".to_string(),
+                format!(
+                    "{},afade=t=in:d=0.02,afade=t=out:st=1.980:d=0.02[a0]",
+                    audio("anullsrc=r=48000:cl=stereo:d=2.000,", "2.000")
+                ),
+                segment_video(0, 1),
+            ]
+        );
+        assert!(chains[3].starts_with("[0:a:0]"));
+    }
+
+    #[test]
+    fn fills_in_silence_when_the_source_has_no_audio() {
+        let silent = ReelFormat { source_audio: false, ..FORMAT };
+        let chains = graph(&segments(&[4.0]), &silent, &HighlightReelOptions::default(), 0.0);
+
+        assert_eq!(
+            chains[1],
+            format!(
+                "{},afade=t=in:d=0.02,afade=t=out:st=3.980:d=0.02[a0]",
+                audio("anullsrc=r=48000:cl=stereo:d=4.000,", "4.000")
+            )
+        );
+        assert_eq!(chains[2..], ["[v0]concat=n=1:v=1:a=0[vout]", "[a0]concat=n=1:v=0:a=1[aout]"]);
+    }
+
+    #[test]
+    fn leaves_out_audio_for_silent_profiles() {
+        let graph = HighlightReelService::filter_graph(&segments(&[4.0, 6.0]), &FORMAT, &HighlightReelOptions::default(), 0.0, false);
+
+        assert!(!graph.contains("[a0]"));
+        assert!(!graph.contains("[aout]"));
+        assert!(graph.ends_with("[v0][v1]concat=n=2:v=1:a=0[vout]"));
+    }
+}
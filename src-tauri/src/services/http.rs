@@ -0,0 +1,284 @@
+use crate::models::NetworkSettings;
+use crate::services::jobs::CancelToken;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A retry about to happen, reported so the UI can show the app isn't hung
+#[derive(Debug, Clone)]
+pub struct RetryEvent {
+    pub attempt: u32,      // The attempt that will run next (2 = first retry)
+    pub max_attempts: u32,
+    pub delay: Duration,
+    pub reason: String,
+}
+
+impl fmt::Display for RetryEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} - retrying in {}s (attempt {} of {})...",
+            self.reason,
+            self.delay.as_secs_f32().ceil(),
+            self.attempt,
+            self.max_attempts
+        )
+    }
+}
+
+type RetryListener = Arc<dyn Fn(&RetryEvent) + Send + Sync>;
+
+/// Fully read HTTP response
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub body: String,
+}
+
+/// Shared HTTP client for OpenAI-style APIs
+///
+/// Applies the configured timeouts and retries transient failures (connection
+/// errors, timeouts, 408/409/429/5xx) with exponential backoff and jitter,
+/// honouring `Retry-After` when the server sends it.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    on_retry: Option<RetryListener>,
+    cancel: CancelToken,
+}
+
+impl HttpClient {
+    pub fn new(settings: &NetworkSettings) -> Result<Self, String> {
+        let client = Client::builder()
+            .connect_timeout(Duration::from_secs(settings.connect_timeout_seconds))
+            .timeout(Duration::from_secs(settings.request_timeout_seconds))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        Ok(Self {
+            client,
+            max_retries: settings.max_retries,
+            base_delay: Duration::from_millis(settings.retry_base_delay_ms),
+            max_delay: Duration::from_secs(settings.retry_max_delay_seconds),
+            on_retry: None,
+            cancel: CancelToken::default(),
+        })
+    }
+
+    /// Report each retry to `listener` (e.g. to emit a progress event)
+    pub fn with_retry_listener(mut self, listener: impl Fn(&RetryEvent) + Send + Sync + 'static) -> Self {
+        self.on_retry = Some(Arc::new(listener));
+        self
+    }
+
+    /// Abort in-flight requests and pending retries when `cancel` fires
+    pub fn with_cancellation(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Send a request, retrying transient failures
+    ///
+    /// `build` is called once per attempt so request bodies that can't be
+    /// cloned (multipart uploads) are rebuilt from scratch each time. Error
+    /// statuses are returned as responses once retries run out, so callers
+    /// can parse the API's error body.
+    pub async fn send_with_retry<F>(&self, build: F) -> Result<HttpResponse, String>
+    where
+        F: Fn(&Client) -> Result<RequestBuilder, String>,
+    {
+        // Cancelling drops the request mid-flight (or the backoff sleep)
+        self.cancel.run(self.send_attempts(build)).await
+    }
+
+    async fn send_attempts<F>(&self, build: F) -> Result<HttpResponse, String>
+    where
+        F: Fn(&Client) -> Result<RequestBuilder, String>,
+    {
+        let max_attempts = self.max_retries + 1;
+        let mut attempt = 1;
+
+        loop {
+            let (reason, retry_after) = match build(&self.client)?.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = Self::retry_after(response.headers(), self.max_delay);
+                    let body = response.text().await
+                        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+                    if !Self::is_retryable_status(status, &body) || attempt >= max_attempts {
+                        return Ok(HttpResponse { status, body });
+                    }
+
+                    let reason = match status {
+                        StatusCode::TOO_MANY_REQUESTS => "Rate limited by the API (HTTP 429)".to_string(),
+                        _ => format!("Server error (HTTP {})", status.as_u16()),
+                    };
+                    (reason, retry_after)
+                }
+                Err(e) => {
+                    let transient = e.is_timeout() || e.is_connect() || e.is_request();
+                    if !transient || attempt >= max_attempts {
+                        return Err(e.to_string());
+                    }
+
+                    let reason = if e.is_timeout() {
+                        "Request timed out".to_string()
+                    } else {
+                        "Connection failed".to_string()
+                    };
+                    (reason, None)
+                }
+            };
+
+            attempt += 1;
+            let delay = retry_after
+                .unwrap_or_else(|| self.backoff_delay(attempt - 1))
+                .min(self.max_delay);
+
+            let event = RetryEvent {
+                attempt,
+                max_attempts,
+                delay,
+                reason,
+            };
+            println!("⚠️  {}", event);
+            if let Some(on_retry) = &self.on_retry {
+                on_retry(&event);
+            }
+
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// 429 and 5xx are transient, except when the account is simply out of quota
+    fn is_retryable_status(status: StatusCode, body: &str) -> bool {
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => !body.contains("insufficient_quota"),
+            StatusCode::REQUEST_TIMEOUT | StatusCode::CONFLICT => true,
+            _ => status.is_server_error(),
+        }
+    }
+
+    /// Server-requested wait from `retry-after-ms` (OpenAI) or `Retry-After` seconds
+    ///
+    /// Capped at `max_delay`; values that aren't finite numbers (`inf`, `NaN`) are ignored.
+    fn retry_after(headers: &HeaderMap, max_delay: Duration) -> Option<Duration> {
+        let header = |name: &str| {
+            headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok().filter(|value| value.is_finite())
+        };
+        // Anything too large for a Duration is well past the cap anyway
+        let capped = |seconds: f64| {
+            Duration::try_from_secs_f64(seconds.max(0.0))
+                .unwrap_or(max_delay)
+                .min(max_delay)
+        };
+
+        header("retry-after-ms")
+            .map(|ms| capped(ms / 1000.0))
+            .or_else(|| header("retry-after").map(capped))
+    }
+
+    /// Exponential backoff with jitter: a random delay in [50%, 100%] of base * 2^(retry - 1)
+    fn backoff_delay(&self, retry: u32) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_delay);
+
+        // uuid v4 is already a random source, so no extra dependency is needed
+        let jitter = (uuid::Uuid::new_v4().as_u128() % 1000) as f64 / 1000.0;
+        exponential.mul_f64(0.5 + jitter * 0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    const MAX_DELAY: Duration = Duration::from_secs(60);
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn retry_after(pairs: &[(&'static str, &str)]) -> Option<Duration> {
+        HttpClient::retry_after(&headers(pairs), MAX_DELAY)
+    }
+
+    #[test]
+    fn reads_retry_after_headers() {
+        assert_eq!(retry_after(&[("retry-after", "2")]), Some(Duration::from_secs(2)));
+        assert_eq!(retry_after(&[("retry-after", " 1.5 ")]), Some(Duration::from_millis(1500)));
+        // OpenAI's millisecond header wins
+        assert_eq!(
+            retry_after(&[("retry-after-ms", "250"), ("retry-after", "2")]),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(retry_after(&[("retry-after", "-3")]), Some(Duration::ZERO));
+        assert_eq!(retry_after(&[]), None);
+        // HTTP dates aren't supported, so backoff applies
+        assert_eq!(retry_after(&[("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")]), None);
+    }
+
+    #[test]
+    fn caps_and_rejects_unusable_retry_after_values() {
+        assert_eq!(retry_after(&[("retry-after", "3600")]), Some(MAX_DELAY));
+        assert_eq!(retry_after(&[("retry-after", "1e30")]), Some(MAX_DELAY));
+        assert_eq!(retry_after(&[("retry-after-ms", "1e300")]), Some(MAX_DELAY));
+        assert_eq!(retry_after(&[("retry-after", "inf")]), None);
+        assert_eq!(retry_after(&[("retry-after", "NaN")]), None);
+        assert_eq!(retry_after(&[("retry-after-ms", "inf"), ("retry-after", "4")]), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn retries_transient_statuses_only() {
+        for status in [408, 409, 429, 500, 502, 503, 504] {
+            assert!(HttpClient::is_retryable_status(StatusCode::from_u16(status).unwrap(), ""), "{}", status);
+        }
+        for status in [200, 400, 401, 403, 404, 422] {
+            assert!(!HttpClient::is_retryable_status(StatusCode::from_u16(status).unwrap(), ""), "{}", status);
+        }
+    }
+
+    #[test]
+    fn does_not_retry_when_out_of_quota() {
+        let body = r#"{"error": {"type": "insufficient_quota", "message": "You exceeded your current quota"}}"#;
+        assert!(!HttpClient::is_retryable_status(StatusCode::TOO_MANY_REQUESTS, body));
+
+        let rate_limited = r#"{"error": {"type": "requests", "message": "Rate limit reached"}}"#;
+        assert!(HttpClient::is_retryable_status(StatusCode::TOO_MANY_REQUESTS, rate_limited));
+    }
+
+    #[test]
+    fn backs_off_exponentially_with_jitter_up_to_the_cap() {
+        let client = HttpClient::new(&NetworkSettings {
+            retry_base_delay_ms: 1000,
+            retry_max_delay_seconds: 10,
+            ..Default::default()
+        })
+        .unwrap();
+
+        for _ in 0..20 {
+            let first = client.backoff_delay(1);
+            assert!(first >= Duration::from_millis(500) && first <= Duration::from_secs(1), "{:?}", first);
+
+            let third = client.backoff_delay(3);
+            assert!(third >= Duration::from_secs(2) && third <= Duration::from_secs(4), "{:?}", third);
+
+            // 2^9 seconds, capped at 10s before jitter
+            let tenth = client.backoff_delay(10);
+            assert!(tenth >= Duration::from_secs(5) && tenth <= Duration::from_secs(10), "{:?}", tenth);
+        }
+        assert!(client.backoff_delay(u32::MAX) <= Duration::from_secs(10));
+    }
+}
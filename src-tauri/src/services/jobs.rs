@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tauri::api::process::{CommandChild, CommandEvent};
+use tokio::sync::{mpsc::Receiver, watch};
+
+/// Error returned by a job that was cancelled, so the UI can tell it apart
+/// from a failure
+pub const JOB_CANCELLED: &str = "cancelled";
+
+/// Shared flag a long-running job checks to see if it should stop
+#[derive(Clone)]
+pub struct CancelToken {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self {
+            sender: Arc::new(watch::channel(false).0),
+        }
+    }
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Resolves once the job is cancelled (never, if it isn't)
+    pub async fn cancelled(&self) {
+        let mut receiver = self.sender.subscribe();
+        let _ = receiver.wait_for(|cancelled| *cancelled).await;
+    }
+
+    /// Run `future` unless the job is cancelled first, in which case it is dropped
+    ///
+    /// Dropping a reqwest future aborts the request, so this is how pending
+    /// API calls are stopped.
+    pub async fn run<T>(&self, future: impl Future<Output = Result<T, String>>) -> Result<T, String> {
+        tokio::select! {
+            result = future => result,
+            _ = self.cancelled() => Err(JOB_CANCELLED.to_string()),
+        }
+    }
+
+    /// Next event from a sidecar, killing the process if the job is cancelled first
+    pub async fn next_event(
+        &self,
+        rx: &mut Receiver<CommandEvent>,
+        child: &mut Option<CommandChild>,
+    ) -> Result<Option<CommandEvent>, String> {
+        tokio::select! {
+            event = rx.recv() => Ok(event),
+            _ = self.cancelled() => {
+                if let Some(child) = child.take() {
+                    println!("⚠️  Job cancelled, killing process {}", child.pid());
+                    let _ = child.kill();
+                }
+                Err(JOB_CANCELLED.to_string())
+            }
+        }
+    }
+}
+
+/// Running clip generation and transcription jobs, kept in Tauri managed state
+///
+/// The frontend picks the job ID when it starts a job and passes the same ID
+/// to `cancel_job`.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, CancelToken>>,
+}
+
+impl JobRegistry {
+    /// Register a job and return the token it should watch
+    pub fn start(&self, job_id: &str) -> CancelToken {
+        let token = CancelToken::default();
+        self.jobs.lock().unwrap().insert(job_id.to_string(), token.clone());
+        println!("Job started: {}", job_id);
+        token
+    }
+
+    /// Signal a running job to stop; returns false if no such job is running
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.jobs.lock().unwrap().get(job_id) {
+            Some(token) => {
+                println!("Cancelling job: {}", job_id);
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Unregister a job, reporting any error after a cancel as `JOB_CANCELLED`
+    ///
+    /// A killed process or aborted request surfaces as whatever error the
+    /// interrupted step produced, so the token decides the outcome.
+    pub fn finish<T>(&self, job_id: &str, result: Result<T, String>) -> Result<T, String> {
+        let token = self.jobs.lock().unwrap().remove(job_id);
+        let cancelled = token.map(|token| token.is_cancelled()).unwrap_or(false);
+
+        match result {
+            Err(_) if cancelled => {
+                println!("⚠️  Job cancelled: {}", job_id);
+                Err(JOB_CANCELLED.to_string())
+            }
+            result => result,
+        }
+    }
+}
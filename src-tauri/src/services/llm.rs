@@ -0,0 +1,464 @@
+use crate::models::{AnalysisSettings, LlmProviderKind};
+use crate::services::http::{HttpClient, HttpResponse};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A language model backend that can answer a clip analysis prompt
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Human-readable provider description for logs
+    fn describe(&self) -> String;
+
+    /// Send a single prompt and return the model's text output
+    async fn complete(&self, request: &CompletionRequest<'_>) -> Result<String, LlmError>;
+}
+
+/// Prompt plus an optional JSON schema the output must follow
+pub struct CompletionRequest<'a> {
+    pub prompt: &'a str,
+    pub response_schema: Option<&'a ResponseSchema>,
+}
+
+/// Named JSON schema for structured outputs
+pub struct ResponseSchema {
+    pub name: &'static str,
+    pub schema: serde_json::Value,
+}
+
+/// Failure talking to an LLM provider
+#[derive(Debug)]
+pub enum LlmError {
+    /// The request never got a response (DNS, connection refused, timeout...)
+    Request(String),
+    /// The API answered with an error object
+    Api {
+        status: u16,
+        error_type: Option<String>,
+        code: Option<String>,
+        message: String,
+    },
+    /// The model declined to answer
+    Refusal(String),
+    /// The response didn't have the expected shape
+    InvalidResponse(String),
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmError::Request(message) => write!(f, "API request failed: {}", message),
+            LlmError::Api { status, error_type, code, message } => {
+                write!(f, "API error ({}", status)?;
+                if let Some(code) = code.as_ref().or(error_type.as_ref()) {
+                    write!(f, ", {}", code)?;
+                }
+                write!(f, "): {}", message)
+            }
+            LlmError::Refusal(message) => write!(f, "Model refused the request: {}", message),
+            LlmError::InvalidResponse(message) => write!(f, "Unexpected API response: {}", message),
+        }
+    }
+}
+
+impl From<LlmError> for String {
+    fn from(error: LlmError) -> String {
+        error.to_string()
+    }
+}
+
+// OpenAI error body: {"error": {"message": ..., "type": ..., "code": ...}}
+// Successful Responses API bodies also carry "error": null
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    #[serde(default)]
+    error: Option<ApiErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorDetail {
+    #[serde(default)]
+    message: String,
+    #[serde(rename = "type", default)]
+    error_type: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_error_code")]
+    code: Option<String>,
+}
+
+/// Error codes are strings on OpenAI but numbers on some compatible servers
+fn deserialize_error_code<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(match value {
+        Some(serde_json::Value::String(code)) => Some(code),
+        Some(serde_json::Value::Null) | None => None,
+        Some(other) => Some(other.to_string()),
+    })
+}
+
+/// Check a response body, turning HTTP errors and error objects into `LlmError::Api`
+fn read_response_body(response: HttpResponse) -> Result<String, LlmError> {
+    let HttpResponse { status, body: response_text } = response;
+
+    println!("=== RAW API RESPONSE ===");
+    println!("{}", response_text);
+    println!("=== END RAW RESPONSE ===");
+
+    let error_detail = serde_json::from_str::<ApiErrorBody>(&response_text)
+        .ok()
+        .and_then(|body| body.error);
+
+    match error_detail {
+        Some(detail) => Err(LlmError::Api {
+            status: status.as_u16(),
+            error_type: detail.error_type,
+            code: detail.code,
+            message: detail.message,
+        }),
+        None if !status.is_success() => Err(LlmError::Api {
+            status: status.as_u16(),
+            error_type: None,
+            code: None,
+            message: response_text,
+        }),
+        None => Ok(response_text),
+    }
+}
+
+/// Build the provider selected in settings
+pub fn provider_from_settings(
+    settings: &AnalysisSettings,
+    openai_api_key: Option<String>,
+    http: HttpClient,
+) -> Result<Box<dyn LlmProvider>, String> {
+    let base_url = settings.resolved_base_url();
+
+    match settings.provider {
+        LlmProviderKind::Responses => {
+            let api_key = openai_api_key
+                .ok_or("No API key configured. Please add your OpenAI API key in Settings.")?;
+            Ok(Box::new(ResponsesApiProvider {
+                http: http.clone(),
+                base_url,
+                api_key,
+                model: settings.model.clone(),
+                reasoning_effort: settings.reasoning_effort.clone(),
+                verbosity: settings.verbosity.clone(),
+            }))
+        }
+        LlmProviderKind::ChatCompletions => {
+            let api_key = openai_api_key
+                .ok_or("No API key configured. Please add your OpenAI API key in Settings.")?;
+            Ok(Box::new(ChatCompletionsProvider {
+                http: http.clone(),
+                base_url,
+                api_key: Some(api_key),
+                model: settings.model.clone(),
+                reasoning_effort: Some(settings.reasoning_effort.clone()),
+                verbosity: Some(settings.verbosity.clone()),
+                structured_output: true,
+            }))
+        }
+        LlmProviderKind::OpenaiCompatible => Ok(Box::new(OpenAICompatibleProvider {
+            inner: ChatCompletionsProvider {
+                http: http.clone(),
+                base_url,
+                api_key: settings.api_key.clone().filter(|k| !k.is_empty()),
+                model: settings.model.clone(),
+                // Local servers don't understand OpenAI reasoning parameters
+                reasoning_effort: None,
+                verbosity: None,
+                // Older servers reject json_schema response formats
+                structured_output: settings.structured_output,
+            },
+        })),
+    }
+}
+
+// GPT-5 Responses API request structure
+#[derive(Debug, Serialize)]
+struct ResponseRequest<'a> {
+    model: &'a str,
+    input: &'a str,
+    reasoning: ReasoningConfig<'a>,
+    text: TextConfig<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReasoningConfig<'a> {
+    effort: &'a str, // "minimal", "low", "medium", "high"
+}
+
+#[derive(Debug, Serialize)]
+struct TextConfig<'a> {
+    verbosity: &'a str, // "low", "medium", "high"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<ResponseTextFormat<'a>>,
+}
+
+// Structured output format for the Responses API (schema fields sit beside "type")
+#[derive(Debug, Serialize)]
+struct ResponseTextFormat<'a> {
+    #[serde(rename = "type")]
+    format_type: &'a str, // "json_schema"
+    name: &'a str,
+    schema: &'a serde_json::Value,
+    strict: bool,
+}
+
+// GPT-5 Responses API response structure (actual format from API)
+#[derive(Debug, Deserialize)]
+struct ResponseApiResponse {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    incomplete_details: Option<serde_json::Value>,
+    output: Vec<ResponseOutput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseOutput {
+    #[serde(rename = "type")]
+    output_type: String,
+    #[serde(default)]
+    content: Vec<ContentItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentItem {
+    #[serde(rename = "type")]
+    content_type: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    refusal: Option<String>,
+}
+
+/// OpenAI Responses API (`POST {base_url}/responses`)
+pub struct ResponsesApiProvider {
+    http: HttpClient,
+    base_url: String,
+    api_key: String,
+    model: String,
+    reasoning_effort: String,
+    verbosity: String,
+}
+
+#[async_trait]
+impl LlmProvider for ResponsesApiProvider {
+    fn describe(&self) -> String {
+        format!(
+            "Responses API at {} (model: {}, reasoning effort: {}, verbosity: {})",
+            self.base_url, self.model, self.reasoning_effort, self.verbosity
+        )
+    }
+
+    async fn complete(&self, request: &CompletionRequest<'_>) -> Result<String, LlmError> {
+        let body = ResponseRequest {
+            model: &self.model,
+            input: request.prompt,
+            reasoning: ReasoningConfig {
+                effort: &self.reasoning_effort,
+            },
+            text: TextConfig {
+                verbosity: &self.verbosity,
+                format: request.response_schema.map(|schema| ResponseTextFormat {
+                    format_type: "json_schema",
+                    name: schema.name,
+                    schema: &schema.schema,
+                    strict: true,
+                }),
+            },
+        };
+
+        let response = self.http
+            .send_with_retry(|client| {
+                Ok(client
+                    .post(format!("{}/responses", self.base_url))
+                    .header("Authorization", format!("Bearer {}", self.api_key))
+                    .header("Content-Type", "application/json")
+                    .json(&body))
+            })
+            .await
+            .map_err(LlmError::Request)?;
+
+        let response_text = read_response_body(response)?;
+
+        let api_response: ResponseApiResponse = serde_json::from_str(&response_text)
+            .map_err(|e| LlmError::InvalidResponse(format!("{}. Response was: {}", e, response_text)))?;
+
+        if api_response.status.as_deref() == Some("incomplete") {
+            return Err(LlmError::InvalidResponse(format!(
+                "Response incomplete: {}",
+                api_response.incomplete_details.map(|d| d.to_string()).unwrap_or_default()
+            )));
+        }
+
+        // Extract text from nested structure: output[?].content[?] where type="message"
+        let content = api_response.output.iter()
+            .filter(|o| o.output_type == "message")
+            .flat_map(|msg| msg.content.iter())
+            .find(|c| c.content_type == "output_text" || c.content_type == "refusal")
+            .ok_or_else(|| LlmError::InvalidResponse("No output_text found in response".to_string()))?;
+
+        if content.content_type == "refusal" {
+            return Err(LlmError::Refusal(content.refusal.clone().unwrap_or_default()));
+        }
+
+        Ok(content.text.clone())
+    }
+}
+
+// Chat Completions API request structure
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verbosity: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ChatResponseFormat<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+// Structured output format for Chat Completions (schema nested under "json_schema")
+#[derive(Debug, Serialize)]
+struct ChatResponseFormat<'a> {
+    #[serde(rename = "type")]
+    format_type: &'a str, // "json_schema"
+    json_schema: ChatJsonSchema<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatJsonSchema<'a> {
+    name: &'a str,
+    schema: &'a serde_json::Value,
+    strict: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    refusal: Option<String>,
+}
+
+/// OpenAI Chat Completions API (`POST {base_url}/chat/completions`)
+pub struct ChatCompletionsProvider {
+    http: HttpClient,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    reasoning_effort: Option<String>,
+    verbosity: Option<String>,
+    structured_output: bool,
+}
+
+#[async_trait]
+impl LlmProvider for ChatCompletionsProvider {
+    fn describe(&self) -> String {
+        format!(
+            "Chat Completions API at {} (model: {}, reasoning effort: {}, verbosity: {})",
+            self.base_url,
+            self.model,
+            self.reasoning_effort.as_deref().unwrap_or("default"),
+            self.verbosity.as_deref().unwrap_or("default")
+        )
+    }
+
+    async fn complete(&self, request: &CompletionRequest<'_>) -> Result<String, LlmError> {
+        let body = ChatRequest {
+            model: &self.model,
+            messages: vec![ChatMessage {
+                role: "user",
+                content: request.prompt,
+            }],
+            reasoning_effort: self.reasoning_effort.as_deref(),
+            verbosity: self.verbosity.as_deref(),
+            response_format: request
+                .response_schema
+                .filter(|_| self.structured_output)
+                .map(|schema| ChatResponseFormat {
+                    format_type: "json_schema",
+                    json_schema: ChatJsonSchema {
+                        name: schema.name,
+                        schema: &schema.schema,
+                        strict: true,
+                    },
+                }),
+        };
+
+        let response = self.http
+            .send_with_retry(|client| {
+                let mut builder = client
+                    .post(format!("{}/chat/completions", self.base_url))
+                    .header("Content-Type", "application/json")
+                    .json(&body);
+                if let Some(api_key) = &self.api_key {
+                    builder = builder.header("Authorization", format!("Bearer {}", api_key));
+                }
+                Ok(builder)
+            })
+            .await
+            .map_err(|e| LlmError::Request(format!("{} ({})", e, self.base_url)))?;
+
+        let response_text = read_response_body(response)?;
+
+        let chat_response: ChatResponse = serde_json::from_str(&response_text)
+            .map_err(|e| LlmError::InvalidResponse(format!("{}. Response was: {}", e, response_text)))?;
+
+        let message = chat_response.choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message)
+            .ok_or_else(|| LlmError::InvalidResponse("No choices in response".to_string()))?;
+
+        if let Some(refusal) = message.refusal {
+            return Err(LlmError::Refusal(refusal));
+        }
+
+        message.content
+            .ok_or_else(|| LlmError::InvalidResponse("No message content found in response".to_string()))
+    }
+}
+
+/// Self-hosted OpenAI-compatible server such as Ollama, llama.cpp server or vLLM
+///
+/// Speaks the Chat Completions protocol, but needs no API key and skips
+/// OpenAI-only parameters, so transcripts never leave the configured host.
+pub struct OpenAICompatibleProvider {
+    inner: ChatCompletionsProvider,
+}
+
+#[async_trait]
+impl LlmProvider for OpenAICompatibleProvider {
+    fn describe(&self) -> String {
+        format!(
+            "OpenAI-compatible server at {} (model: {}, structured output: {})",
+            self.inner.base_url, self.inner.model, self.inner.structured_output
+        )
+    }
+
+    async fn complete(&self, request: &CompletionRequest<'_>) -> Result<String, LlmError> {
+        self.inner.complete(request).await
+    }
+}
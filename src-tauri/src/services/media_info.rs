@@ -0,0 +1,370 @@
+use crate::models::{AudioStreamInfo, MediaInfo, Timestamp, VideoStreamInfo};
+use crate::services::CancelToken;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tauri::api::process::{Command, CommandEvent};
+
+/// `ffprobe -print_format json -show_format -show_streams` output (the parts we use)
+#[derive(Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+    format: Option<ProbeFormat>,
+}
+
+#[derive(Deserialize)]
+struct ProbeStream {
+    index: u32,
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    profile: Option<String>,
+    level: Option<i64>,
+    time_base: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    avg_frame_rate: Option<String>,
+    r_frame_rate: Option<String>,
+    pix_fmt: Option<String>,
+    bit_rate: Option<String>,
+    channels: Option<u32>,
+    channel_layout: Option<String>,
+    sample_rate: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    #[serde(default)]
+    disposition: HashMap<String, i64>,
+    #[serde(default)]
+    side_data_list: Vec<HashMap<String, serde_json::Value>>,
+}
+
+impl ProbeStream {
+    /// Rotation from the display matrix (or the older `rotate` tag), e.g. 90 for portrait phone video
+    fn rotation(&self) -> i64 {
+        self.side_data_list
+            .iter()
+            .find_map(|side_data| side_data.get("rotation").and_then(|rotation| rotation.as_i64()))
+            .or_else(|| self.tags.get("rotate").and_then(|rotate| rotate.parse().ok()))
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Deserialize)]
+struct ProbeFormat {
+    format_name: Option<String>,
+    start_time: Option<String>,
+    duration: Option<String>,
+    size: Option<String>,
+    bit_rate: Option<String>,
+}
+
+/// Inspects media files with the bundled ffprobe sidecar
+pub struct MediaInfoService;
+
+impl MediaInfoService {
+    /// Duration, container, and video/audio stream details of a file
+    pub async fn probe(path: &str) -> Result<MediaInfo, String> {
+        println!("=== ffprobe: {} ===", path);
+        
+        let args = [
+            "-v", "error",
+            "-print_format", "json",
+            "-show_format",
+            "-show_streams",
+            path,
+        ];
+        let output = Self::run_ffprobe(&args, &CancelToken::default()).await?;
+        let json: serde_json::Value = serde_json::from_str(&output)
+            .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+        let info = Self::parse(path, &json)?;
+        
+        println!(
+            "Duration: {}, video: {}, audio tracks: {}",
+            info.duration,
+            info.video_codec().unwrap_or("none"),
+            info.audio_tracks.len()
+        );
+        Ok(info)
+    }
+    
+    /// Map ffprobe's `-show_format -show_streams` JSON for `path` to a `MediaInfo`
+    fn parse(path: &str, json: &serde_json::Value) -> Result<MediaInfo, String> {
+        let probe = ProbeOutput::deserialize(json)
+            .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+        
+        let format = probe.format.ok_or("ffprobe found no container information")?;
+        let duration = format.duration
+            .as_deref()
+            .and_then(|duration| duration.parse::<f64>().ok())
+            .map(Timestamp::from_secs_f64)
+            .ok_or("Could not determine media duration")?;
+        
+        // Cover art shows up as a one-frame video stream
+        let video = probe.streams
+            .iter()
+            .filter(|stream| stream.codec_type.as_deref() == Some("video"))
+            .find(|stream| stream.disposition.get("attached_pic").copied().unwrap_or(0) == 0)
+            .map(|stream| {
+                // FFmpeg applies the rotation when decoding, so report the upright size
+                let (width, height) = (stream.width.unwrap_or(0), stream.height.unwrap_or(0));
+                let (width, height) = match stream.rotation().rem_euclid(180) {
+                    90 => (height, width),
+                    _ => (width, height),
+                };
+                VideoStreamInfo {
+                    codec: stream.codec_name.clone().unwrap_or_default(),
+                    profile: stream.profile.clone(),
+                    width,
+                    height,
+                    frame_rate: stream.avg_frame_rate
+                        .as_deref()
+                        .and_then(Self::parse_rate)
+                        .or_else(|| stream.r_frame_rate.as_deref().and_then(Self::parse_rate)),
+                    pixel_format: stream.pix_fmt.clone(),
+                    bit_rate: stream.bit_rate.as_deref().and_then(|rate| rate.parse().ok()),
+                    // -99 means unknown
+                    level: stream.level.filter(|level| *level > 0),
+                    time_base: stream.time_base.clone(),
+                }
+            });
+        
+        let audio_tracks = probe.streams
+            .iter()
+            .filter(|stream| stream.codec_type.as_deref() == Some("audio"))
+            .map(|stream| AudioStreamInfo {
+                index: stream.index,
+                codec: stream.codec_name.clone().unwrap_or_default(),
+                channels: stream.channels.unwrap_or(0),
+                channel_layout: stream.channel_layout.clone(),
+                sample_rate: stream.sample_rate.as_deref().and_then(|rate| rate.parse().ok()),
+                language: stream.tags.get("language").cloned().filter(|language| language != "und"),
+            })
+            .collect();
+        
+        Ok(MediaInfo {
+            path: path.to_string(),
+            duration,
+            start_time: format.start_time
+                .as_deref()
+                .and_then(|start| start.parse().ok())
+                .unwrap_or(0.0),
+            format_name: format.format_name.unwrap_or_default(),
+            size_bytes: format.size.as_deref().and_then(|size| size.parse().ok()),
+            bit_rate: format.bit_rate.as_deref().and_then(|rate| rate.parse().ok()),
+            video,
+            audio_tracks,
+        })
+    }
+    
+    /// Keyframe times of the first video stream between `from` and `to`
+    ///
+    /// Only keyframes are decoded, so scanning a few seconds is quick.
+    pub async fn keyframes(
+        info: &MediaInfo,
+        from: Timestamp,
+        to: Timestamp,
+        cancel: &CancelToken,
+    ) -> Result<Vec<Timestamp>, String> {
+        // ffprobe works in container time, clips in time since the first frame
+        let interval = format!(
+            "{:.3}%{:.3}",
+            from.as_duration().as_secs_f64() + info.start_time,
+            to.as_duration().as_secs_f64() + info.start_time
+        );
+        let args = [
+            "-v", "error",
+            "-select_streams", "v:0",
+            "-skip_frame", "nokey",
+            "-read_intervals", &interval,
+            "-show_entries", "frame=pts_time,best_effort_timestamp_time",
+            "-of", "csv=p=0",
+            &info.path,
+        ];
+        let output = Self::run_ffprobe(&args, cancel).await?;
+        let keyframes = Self::parse_keyframes(&output, info.start_time, from, to);
+        
+        println!("Keyframes between {} and {}: {}", from, to, keyframes.len());
+        Ok(keyframes)
+    }
+    
+    /// Keyframe times from ffprobe's `pts_time,best_effort_timestamp_time` csv, relative to the first frame
+    fn parse_keyframes(output: &str, start_time: f64, from: Timestamp, to: Timestamp) -> Vec<Timestamp> {
+        // Round up so seeking to a keyframe never lands on the one before it
+        output
+            .lines()
+            .filter_map(|line| line.split(',').find_map(|value| value.trim().parse::<f64>().ok()))
+            .map(|seconds| seconds - start_time)
+            .filter(|seconds| *seconds >= 0.0)
+            .map(|seconds| Timestamp::from_millis((seconds * 1000.0).ceil() as u64))
+            .filter(|keyframe| *keyframe >= from && *keyframe <= to)
+            .collect()
+    }
+    
+    /// "30000/1001" -> 29.97; "0/0" (unknown) -> None
+    fn parse_rate(rate: &str) -> Option<f64> {
+        let (numerator, denominator) = rate.split_once('/')?;
+        let numerator: f64 = numerator.parse().ok()?;
+        let denominator: f64 = denominator.parse().ok()?;
+        (numerator > 0.0 && denominator > 0.0).then(|| numerator / denominator)
+    }
+    
+    /// Run ffprobe to completion and return its stdout
+    async fn run_ffprobe(args: &[&str], cancel: &CancelToken) -> Result<String, String> {
+        let (mut rx, child) = Command::new_sidecar("ffprobe")
+            .map_err(|e| format!("Failed to find ffprobe: {}", e))?
+            .args(args)
+            .spawn()
+            .map_err(|e| format!("Failed to spawn ffprobe: {}", e))?;
+        
+        let mut child = Some(child);
+        let mut stdout = String::new();
+        let mut stderr_lines = Vec::new();
+        
+        while let Some(event) = cancel.next_event(&mut rx, &mut child).await? {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    stdout.push_str(&line);
+                    stdout.push('\n');
+                }
+                CommandEvent::Stderr(line) => stderr_lines.push(line),
+                CommandEvent::Error(error) => {
+                    return Err(format!("ffprobe error: {}", error));
+                }
+                CommandEvent::Terminated(payload) => {
+                    if payload.code != Some(0) {
+                        let detail = stderr_lines.join("\n");
+                        println!("ffprobe failed: {}", detail);
+                        return Err(match stderr_lines.last() {
+                            Some(line) => format!("ffprobe failed: {}", line),
+                            None => format!("ffprobe failed with code: {:?}", payload.code),
+                        });
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+        
+        Ok(stdout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: &str) -> serde_json::Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn ms(millis: &[u64]) -> Vec<Timestamp> {
+        millis.iter().map(|millis| Timestamp::from_millis(*millis)).collect()
+    }
+
+    #[test]
+    fn parses_rotated_phone_video() {
+        let json = fixture(include_str!("../../../tests/fixtures/ffprobe/iphone-portrait.json"));
+        let info = MediaInfoService::parse("IMG_4127.MOV", &json).unwrap();
+
+        assert_eq!(info.path, "IMG_4127.MOV");
+        assert_eq!(info.duration, Timestamp::from_millis(11_960));
+        assert_eq!(info.start_time, 0.0);
+        assert_eq!(info.format_name, "mov,mp4,m4a,3gp,3g2,mj2");
+        assert_eq!(info.size_bytes, Some(12_044_863));
+        assert_eq!(info.bit_rate, Some(8_056_764));
+
+        // The display matrix turns the 1920x1080 frames upright
+        let video = info.video.unwrap();
+        assert_eq!((video.codec.as_str(), video.profile.as_deref()), ("hevc", Some("Main 10")));
+        assert_eq!((video.width, video.height), (1080, 1920));
+        assert!((video.frame_rate.unwrap() - 29.9).abs() < 0.01);
+        assert_eq!(video.pixel_format.as_deref(), Some("yuv420p10le"));
+        assert_eq!(video.bit_rate, Some(7_864_231));
+        assert_eq!(video.level, Some(123));
+        assert_eq!(video.time_base.as_deref(), Some("1/600"));
+
+        // The metadata track isn't audio, and "und" isn't a language
+        assert_eq!(info.audio_tracks.len(), 1);
+        let audio = &info.audio_tracks[0];
+        assert_eq!((audio.index, audio.codec.as_str(), audio.channels), (1, "aac", 2));
+        assert_eq!(audio.channel_layout.as_deref(), Some("stereo"));
+        assert_eq!(audio.sample_rate, Some(44_100));
+        assert_eq!(audio.language, None);
+    }
+
+    #[test]
+    fn skips_cover_art() {
+        let json = fixture(include_str!("../../../tests/fixtures/ffprobe/cover-art.json"));
+        let info = MediaInfoService::parse("lecture.mkv", &json).unwrap();
+
+        assert_eq!(info.duration, Timestamp::from_millis(2_533_041));
+        assert_eq!(info.start_time, -0.007);
+        assert_eq!(info.format_name, "matroska,webm");
+
+        // Matroska has no average frame rate; fall back to the stream's base rate
+        let video = info.video.unwrap();
+        assert_eq!(video.codec, "h264");
+        assert_eq!((video.width, video.height), (1280, 720));
+        assert_eq!(video.frame_rate, Some(25.0));
+        assert_eq!(video.bit_rate, None);
+        assert_eq!(video.level, None);
+
+        let tracks: Vec<_> = info.audio_tracks
+            .iter()
+            .map(|track| (track.index, track.codec.as_str(), track.channels, track.language.as_deref()))
+            .collect();
+        assert_eq!(tracks, [(2, "opus", 6, Some("eng")), (3, "aac", 2, Some("deu"))]);
+    }
+
+    #[test]
+    fn parses_audio_only_files() {
+        let json = fixture(r#"{
+            "streams": [
+                {"index": 0, "codec_name": "mp3", "codec_type": "audio", "sample_rate": "44100", "channels": 1},
+                {"index": 1, "codec_name": "png", "codec_type": "video", "width": 500, "height": 500,
+                 "disposition": {"default": 0, "attached_pic": 1}}
+            ],
+            "format": {"format_name": "mp3", "duration": "184.320000"}
+        }"#);
+        let info = MediaInfoService::parse("episode.mp3", &json).unwrap();
+
+        assert!(info.video.is_none());
+        assert_eq!(info.audio_tracks.len(), 1);
+        assert_eq!(info.duration, Timestamp::from_millis(184_320));
+        assert_eq!(info.size_bytes, None);
+    }
+
+    #[test]
+    fn rejects_files_without_a_duration() {
+        let no_format = fixture(r#"{"streams": []}"#);
+        assert!(MediaInfoService::parse("broken.mp4", &no_format).is_err());
+
+        let no_duration = fixture(r#"{"streams": [], "format": {"format_name": "image2", "duration": "N/A"}}"#);
+        assert!(MediaInfoService::parse("still.png", &no_duration).is_err());
+
+        let not_probe_output = fixture(r#"{"streams": {}}"#);
+        assert!(MediaInfoService::parse("weird.mp4", &not_probe_output).is_err());
+    }
+
+    #[test]
+    fn parses_frame_rates() {
+        assert!((MediaInfoService::parse_rate("30000/1001").unwrap() - 29.97).abs() < 0.001);
+        assert_eq!(MediaInfoService::parse_rate("25/1"), Some(25.0));
+        assert_eq!(MediaInfoService::parse_rate("0/0"), None);
+        assert_eq!(MediaInfoService::parse_rate("25"), None);
+    }
+
+    #[test]
+    fn parses_keyframes() {
+        let output = include_str!("../../../tests/fixtures/ffprobe/keyframes-mpegts.csv");
+
+        // The transport stream starts at 1.4s; frames without a pts use the best effort time
+        let keyframes = MediaInfoService::parse_keyframes(output, 1.4, Timestamp::from_millis(2_000), Timestamp::from_millis(10_500));
+        assert_eq!(keyframes, ms(&[2_000, 4_000, 6_000, 8_000, 10_334]));
+
+        // Keyframes before the first frame's time are dropped
+        let keyframes = MediaInfoService::parse_keyframes(output, 2.0, Timestamp::default(), Timestamp::from_millis(2_000));
+        assert_eq!(keyframes, ms(&[1_400]));
+
+        assert!(MediaInfoService::parse_keyframes("", 0.0, Timestamp::default(), Timestamp::from_millis(1_000)).is_empty());
+    }
+}
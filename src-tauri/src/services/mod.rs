@@ -1,9 +1,30 @@
+pub mod http;
+pub mod jobs;
+pub mod audio_chunker;
 pub mod vtt_parser;
+pub mod srt_parser;
+pub mod transcript;
+pub mod llm;
 pub mod openai;
+pub mod captions;
 pub mod ffmpeg;
+pub mod ffmpeg_args;
+pub mod highlight_reel;
+pub mod media_info;
+pub mod transcription;
 pub mod whisper;
+pub mod whisper_cpp;
 
+pub use http::HttpClient;
+pub use jobs::{CancelToken, JobRegistry};
 pub use vtt_parser::VttParser;
+pub use srt_parser::SrtParser;
+pub use transcript::TranscriptLoader;
+pub use llm::{provider_from_settings, CompletionRequest, LlmError, LlmProvider, ResponseSchema};
 pub use openai::OpenAIService;
-pub use ffmpeg::FFmpegService;
+pub use ffmpeg::{ClipSubtitles, FFmpegService};
+pub use ffmpeg_args::FfmpegArgs;
+pub use highlight_reel::HighlightReelService;
+pub use media_info::MediaInfoService;
+pub use transcription::backend_from_settings;
 pub use whisper::WhisperService;
@@ -1,57 +1,178 @@
-use crate::models::ClipSuggestion;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-
-// GPT-5 Responses API request structure
-#[derive(Debug, Serialize)]
-struct ResponseRequest {
-    model: String,
-    input: String,
-    reasoning: ReasoningConfig,
-    text: TextConfig,
-}
-
-#[derive(Debug, Serialize)]
-struct ReasoningConfig {
-    effort: String, // "minimal", "low", "medium", "high"
-}
-
-#[derive(Debug, Serialize)]
-struct TextConfig {
-    verbosity: String, // "low", "medium", "high"
-}
-
-// GPT-5 Responses API response structure (actual format from API)
-#[derive(Debug, Deserialize)]
-struct ResponseApiResponse {
-    output: Vec<ResponseOutput>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ResponseOutput {
-    #[serde(rename = "type")]
-    output_type: String,
-    #[serde(default)]
-    content: Vec<ContentItem>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ContentItem {
-    #[serde(rename = "type")]
-    content_type: String,
-    #[serde(default)]
-    text: String,
-}
+use crate::models::{language_name, AnalysisSettings, ClipSuggestion, VttCue};
+use crate::services::{CompletionRequest, LlmError, LlmProvider, ResponseSchema, VttParser};
+use serde_json::{json, Value};
+use std::time::Duration;
 
 pub struct OpenAIService;
 
 impl OpenAIService {
+    /// Analyze a transcript of any length
+    ///
+    /// Transcripts that fit in one window go out in a single request. Longer
+    /// ones are split into overlapping windows that each propose candidates
+    /// (map), then candidates are de-duplicated and ranked across the whole
+    /// video (reduce), so the result isn't limited to the first hour.
+    pub async fn analyze_cues(
+        provider: &dyn LlmProvider,
+        cues: &[VttCue],
+        user_context: Option<&str>,
+        language: Option<&str>,
+        settings: &AnalysisSettings,
+    ) -> Result<Vec<ClipSuggestion>, LlmError> {
+        let max_clips = settings.max_clips.max(1);
+        let windows = VttParser::window_cues(
+            cues,
+            Duration::from_secs(u64::from(settings.window_minutes) * 60),
+            Duration::from_secs(u64::from(settings.window_overlap_seconds)),
+        );
+        
+        if windows.len() <= 1 {
+            return Self::analyze_transcript(
+                provider,
+                &VttParser::get_formatted_vtt(cues),
+                user_context,
+                language,
+                &VttParser::get_speakers(cues),
+                &format!("{}-{}", max_clips.min(3), max_clips),
+            ).await;
+        }
+        
+        println!("=== Chunked Analysis: {} windows ===", windows.len());
+        
+        // Map: collect a few candidates from every window
+        let mut candidates: Vec<(usize, ClipSuggestion)> = Vec::new();
+        let mut last_error = None;
+        
+        for (index, window_cues) in windows.iter().enumerate() {
+            println!(
+                "--- Window {}/{}: {} -> {} ({} cues) ---",
+                index + 1,
+                windows.len(),
+                window_cues[0].start_timestamp,
+                window_cues[window_cues.len() - 1].end_timestamp,
+                window_cues.len()
+            );
+            
+            let result = Self::analyze_transcript(
+                provider,
+                &VttParser::get_formatted_vtt(window_cues),
+                user_context,
+                language,
+                &VttParser::get_speakers(window_cues),
+                "1-4",
+            ).await;
+            
+            // One bad window shouldn't throw away the rest of a long run
+            match result {
+                Ok(clips) => candidates.extend(clips.into_iter().map(|clip| (index, clip))),
+                Err(e) => {
+                    println!("⚠️  Window {} failed: {}", index + 1, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        
+        if candidates.is_empty() {
+            return Err(last_error.unwrap_or_else(|| {
+                LlmError::InvalidResponse("No clip candidates found in transcript".to_string())
+            }));
+        }
+        
+        // Reduce: dedupe and rank globally
+        Ok(Self::reduce_candidates(candidates, max_clips))
+    }
+    
+    /// Merge per-window candidates into one bounded, de-duplicated set
+    ///
+    /// The best clip from each window is picked first so every part of the
+    /// video is represented, then remaining slots go to the highest scores.
+    fn reduce_candidates(
+        mut candidates: Vec<(usize, ClipSuggestion)>,
+        max_clips: usize,
+    ) -> Vec<ClipSuggestion> {
+        candidates.sort_by(|(_, a), (_, b)| b.score_or_default().total_cmp(&a.score_or_default()));
+        
+        // Overlapping windows often suggest the same moment twice
+        let mut unique: Vec<(usize, ClipSuggestion)> = Vec::new();
+        for (window, clip) in candidates {
+            if !unique.iter().any(|(_, kept)| Self::is_duplicate(kept, &clip)) {
+                unique.push((window, clip));
+            }
+        }
+        
+        let mut selected: Vec<usize> = Vec::new();
+        let mut covered_windows: Vec<usize> = Vec::new();
+        for (position, (window, _)) in unique.iter().enumerate() {
+            if selected.len() < max_clips && !covered_windows.contains(window) {
+                covered_windows.push(*window);
+                selected.push(position);
+            }
+        }
+        for position in 0..unique.len() {
+            if selected.len() < max_clips && !selected.contains(&position) {
+                selected.push(position);
+            }
+        }
+        
+        let mut clips: Vec<ClipSuggestion> = unique
+            .into_iter()
+            .enumerate()
+            .filter(|(position, _)| selected.contains(position))
+            .map(|(_, (_, clip))| clip)
+            .collect();
+        clips.sort_by_key(|clip| clip.start_time);
+        
+        println!("=== Reduced to {} clips ===", clips.len());
+        clips
+    }
+    
+    /// Two clips are duplicates if they share more than half of the shorter one
+    fn is_duplicate(a: &ClipSuggestion, b: &ClipSuggestion) -> bool {
+        let overlap_start = a.start_time.max(b.start_time);
+        let overlap_end = a.end_time.min(b.end_time);
+        if overlap_end <= overlap_start {
+            return false;
+        }
+        
+        let shorter = (a.end_time - a.start_time).min(b.end_time - b.start_time);
+        (overlap_end - overlap_start) * 2 > shorter
+    }
+    
     pub async fn analyze_transcript(
-        api_key: &str,
+        provider: &dyn LlmProvider,
         vtt_transcript: &str,
         user_context: Option<&str>,
-    ) -> Result<Vec<ClipSuggestion>, String> {
-        let client = Client::new();
+        language: Option<&str>,
+        speakers: &[String],
+        clip_count: &str,
+    ) -> Result<Vec<ClipSuggestion>, LlmError> {
+        // Tell the model who is talking so requests like "clips where the guest
+        // talks about X" can be matched against the voice tags
+        let speaker_section = if speakers.is_empty() {
+            String::new()
+        } else {
+            format!(
+                r#"SPEAKERS: Lines are tagged with the speaker's voice, e.g. "<v {}>". Speakers in this transcript: {}.
+When instructions refer to a person or role (host, guest, interviewer...), use the voice tags to find the lines they speak.
+
+"#,
+                speakers[0],
+                speakers.join(", ")
+            )
+        };
+        
+        // Titles should read naturally next to the footage, so keep them in its language
+        let language_section = match language {
+            Some(code) => {
+                let name = language_name(code);
+                format!(
+                    "LANGUAGE: The transcript is in {}. Read it as {} and write clip titles in {}.\n\n",
+                    name, name, name
+                )
+            }
+            None => String::new(),
+        };
+        let transcript_notes = format!("{}{}", language_section, speaker_section);
         
         // Build prompt with USER INSTRUCTIONS FIRST
         let input = if let Some(user_instructions) = user_context {
@@ -65,23 +186,26 @@ If the user's instructions conflict with any guidance below, ALWAYS prioritize t
 
 TASK: Analyze this VTT video transcript and identify clips to extract.
 
-Output ONLY a valid JSON array. Each object must have:
+Output ONLY valid JSON: an object with a "clips" array. Each clip object must have:
 - "title": Brief, descriptive title (max 50 characters)
-- "start_time": Start timestamp in format HH:MM:SS (matching VTT timestamps)
-- "end_time": End timestamp in format HH:MM:SS (matching VTT timestamps)
+- "start_time": Start timestamp in format HH:MM:SS.mmm (copied from a VTT cue start)
+- "end_time": End timestamp in format HH:MM:SS.mmm (copied from a VTT cue end)
+- "score": How strong the moment is, from 1 (weak) to 10 (must-share)
 
 RULES:
 1. Use EXACT timestamps from the VTT (format: HH:MM:SS.mmm --> HH:MM:SS.mmm)
 2. Each clip should be 10-120 seconds long
-3. Return 3-8 clips maximum
+3. Return {} clips maximum
 4. Ensure end_time is after start_time
-5. DO NOT include any text outside the JSON array
+5. DO NOT include any text outside the JSON
 
-VTT TRANSCRIPT:
+{}VTT TRANSCRIPT:
 {}
 
-Output only the JSON array, nothing else."#,
+Output only the JSON, nothing else."#,
                 user_instructions,
+                clip_count,
+                transcript_notes,
                 vtt_transcript
             )
         } else {
@@ -96,82 +220,319 @@ Focus on:
 - Funny or memorable exchanges
 - Major decisions or announcements
 
-Output ONLY a valid JSON array. Each object must have:
+Output ONLY valid JSON: an object with a "clips" array. Each clip object must have:
 - "title": Brief, descriptive title (max 50 characters)
-- "start_time": Start timestamp in format HH:MM:SS (matching VTT timestamps)
-- "end_time": End timestamp in format HH:MM:SS (matching VTT timestamps)
+- "start_time": Start timestamp in format HH:MM:SS.mmm (copied from a VTT cue start)
+- "end_time": End timestamp in format HH:MM:SS.mmm (copied from a VTT cue end)
+- "score": How strong the moment is, from 1 (weak) to 10 (must-share)
 
 RULES:
 1. Use EXACT timestamps from the VTT (format: HH:MM:SS.mmm --> HH:MM:SS.mmm)
 2. Each clip should be 10-120 seconds long
-3. Return 3-8 clips maximum
+3. Return {} clips maximum
 4. Ensure end_time is after start_time
-5. DO NOT include any text outside the JSON array
+5. DO NOT include any text outside the JSON
 
-VTT TRANSCRIPT:
+{}VTT TRANSCRIPT:
 {}
 
-Output only the JSON array, nothing else."#,
+Output only the JSON, nothing else."#,
+                clip_count,
+                transcript_notes,
                 vtt_transcript
             )
         };
         
-        let request = ResponseRequest {
-            model: "gpt-5-mini".to_string(),
-            input,
-            reasoning: ReasoningConfig {
-                effort: "minimal".to_string(), // Fast analysis, simple task
-            },
-            text: TextConfig {
-                verbosity: "low".to_string(), // Concise JSON output only
-            },
-        };
+        println!("=== LLM Request ===");
+        println!("Provider: {}", provider.describe());
         
-        println!("=== OpenAI API Request ===");
-        println!("Model: gpt-5-mini");
-        println!("Reasoning effort: minimal");
-        println!("Verbosity: low");
-        
-        let response = client
-            .post("https://api.openai.com/v1/responses")
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| format!("API request failed: {}", e))?;
-        
-        // Get raw response text for debugging
-        let response_text = response.text().await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
-        
-        println!("=== RAW API RESPONSE ===");
-        println!("{}", response_text);
-        println!("=== END RAW RESPONSE ===");
-        
-        // Check for actual errors (error field with non-null value)
-        if response_text.contains("\"error\": {") || response_text.contains("\"error\":{") {
-            return Err(format!("OpenAI API error: {}", response_text));
-        }
+        let schema = Self::clip_suggestions_schema();
+        let content = provider.complete(&CompletionRequest {
+            prompt: &input,
+            response_schema: Some(&schema),
+        }).await?;
         
-        // Try to parse the response
-        let api_response: ResponseApiResponse = serde_json::from_str(&response_text)
-            .map_err(|e| format!("Failed to parse response: {}. Response was: {}", e, response_text))?;
+        println!("=== Model Response ===");
+        println!("{}", content);
         
-        // Extract text from nested structure: output[?].content[?].text where type="message" and content_type="output_text"
-        let content = api_response.output.iter()
-            .find(|o| o.output_type == "message")
-            .and_then(|msg| msg.content.iter().find(|c| c.content_type == "output_text"))
-            .map(|c| &c.text)
-            .ok_or("No output_text found in response")?;
+        Self::parse_clip_suggestions(&content)
+    }
+    
+    /// Strict JSON schema for clip suggestions
+    ///
+    /// Strict mode needs an object at the root, so clips are wrapped in `{"clips": [...]}`.
+    fn clip_suggestions_schema() -> ResponseSchema {
+        ResponseSchema {
+            name: "clip_suggestions",
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "clips": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "title": { "type": "string" },
+                                "start_time": { "type": "string" },
+                                "end_time": { "type": "string" },
+                                "score": { "type": "number" }
+                            },
+                            "required": ["title", "start_time", "end_time", "score"],
+                            "additionalProperties": false
+                        }
+                    }
+                },
+                "required": ["clips"],
+                "additionalProperties": false
+            }),
+        }
+    }
+    
+    /// Parse clip suggestions from model output
+    ///
+    /// Accepts the structured `{"clips": [...]}` object or a bare array, and
+    /// otherwise pulls the first usable JSON value out of free text (Markdown
+    /// code fences, a sentence before the JSON...). Individual clips that
+    /// don't parse are skipped instead of failing the whole analysis, but a
+    /// list where none of them parse is an error.
+    fn parse_clip_suggestions(content: &str) -> Result<Vec<ClipSuggestion>, LlmError> {
+        let value = serde_json::from_str::<Value>(content.trim())
+            .ok()
+            .and_then(|value| Self::clip_array(&value).cloned())
+            .or_else(|| {
+                // Prose can hold other arrays ("[1, 2]"), so a bare one needs clips in it
+                Self::json_values_in_text(content).find_map(|value| match &value {
+                    Value::Array(items) if !items.iter().any(Self::is_clip_shaped) => None,
+                    _ => Self::clip_array(&value).cloned(),
+                })
+            })
+            .ok_or_else(|| LlmError::InvalidResponse(format!(
+                "No JSON clip list found in model output: {}",
+                content
+            )))?;
         
-        println!("=== GPT-5 Response ===");
-        println!("{}", content);
+        let suggested = value.len();
+        let clips: Vec<ClipSuggestion> = value
+            .into_iter()
+            .filter_map(|item| match serde_json::from_value::<ClipSuggestion>(item.clone()) {
+                Ok(clip) => Some(clip),
+                Err(e) => {
+                    println!("⚠️  Skipping malformed clip suggestion {}: {}", item, e);
+                    None
+                }
+            })
+            .collect();
         
-        // Try to parse as JSON array directly
-        let clips: Vec<ClipSuggestion> = serde_json::from_str(content)
-            .map_err(|e| format!("Failed to parse clip suggestions: {}. Response was: {}", e, content))?;
+        if clips.is_empty() && suggested > 0 {
+            return Err(LlmError::InvalidResponse(format!(
+                "None of the {} clip suggestions could be read: {}",
+                suggested,
+                content
+            )));
+        }
         
         Ok(clips)
     }
+    
+    /// An object with the fields every clip suggestion has
+    fn is_clip_shaped(item: &Value) -> bool {
+        item.get("start_time").is_some() && item.get("end_time").is_some()
+    }
+    
+    /// The clip list inside a parsed value: either the value itself or its "clips" field
+    fn clip_array(value: &Value) -> Option<&Vec<Value>> {
+        match value {
+            Value::Array(items) => Some(items),
+            Value::Object(fields) => fields.get("clips").and_then(Value::as_array),
+            _ => None,
+        }
+    }
+    
+    /// Iterate over JSON arrays and objects embedded in free text, in order
+    fn json_values_in_text(text: &str) -> impl Iterator<Item = Value> + '_ {
+        text.char_indices()
+            .filter(|(_, c)| *c == '[' || *c == '{')
+            .filter_map(move |(start, _)| {
+                let end = Self::matching_bracket(&text[start..])?;
+                serde_json::from_str(&text[start..start + end]).ok()
+            })
+    }
+    
+    /// Byte length of the balanced JSON array/object at the start of `text`
+    fn matching_bracket(text: &str) -> Option<usize> {
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        
+        for (index, c) in text.char_indices() {
+            if in_string {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            
+            match c {
+                '"' => in_string = true,
+                '[' | '{' => depth += 1,
+                ']' | '}' => {
+                    depth = depth.checked_sub(1)?;
+                    if depth == 0 {
+                        return Some(index + 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Timestamp;
+
+    const CLIPS: &str = r#"[{"title": "Intro [draft]", "start_time": "00:00:05", "end_time": "00:00:45", "score": 8}]"#;
+
+    fn parse(content: &str) -> Vec<ClipSuggestion> {
+        OpenAIService::parse_clip_suggestions(content).unwrap()
+    }
+
+    #[test]
+    fn parses_structured_object_and_bare_array() {
+        let from_object = parse(&format!(r#"{{"clips": {}}}"#, CLIPS));
+        let from_array = parse(CLIPS);
+
+        for clips in [from_object, from_array] {
+            assert_eq!(clips.len(), 1);
+            assert_eq!(clips[0].title, "Intro [draft]");
+            assert_eq!(clips[0].start_time, Timestamp::from_millis(5_000));
+            assert_eq!(clips[0].end_time, Timestamp::from_millis(45_000));
+            assert_eq!(clips[0].score, Some(8.0));
+        }
+        assert!(parse(r#"{"clips": []}"#).is_empty());
+    }
+
+    #[test]
+    fn finds_json_in_code_fences_and_prose() {
+        let fenced = format!("```json\n{}\n```", CLIPS);
+        assert_eq!(parse(&fenced).len(), 1);
+
+        let prose = format!("Here are the clips you asked for:\n{}\nLet me know if you want more!", CLIPS);
+        assert_eq!(parse(&prose).len(), 1);
+
+        let wrapped = format!("Sure. {{\"clips\": {}}} Hope that helps.", CLIPS);
+        assert_eq!(parse(&wrapped)[0].title, "Intro [draft]");
+    }
+
+    #[test]
+    fn skips_arrays_in_prose_that_hold_no_clips() {
+        let content = format!("I picked [3] moments from the first [half]:\n{}", CLIPS);
+        assert_eq!(parse(&content).len(), 1);
+
+        let numbers = "Scores were [7, 8, 9] overall.";
+        assert!(OpenAIService::parse_clip_suggestions(numbers).is_err());
+    }
+
+    #[test]
+    fn skips_malformed_clips_but_fails_when_none_parse() {
+        let mixed = r#"[{"title": "ok", "start_time": "00:01:00", "end_time": "00:01:30"},
+                        {"title": "bad", "start_time": "soon", "end_time": "later"}]"#;
+        assert_eq!(parse(mixed).len(), 1);
+
+        let all_bad = r#"{"clips": [{"title": "bad", "start_time": "soon", "end_time": "later"}, {"name": "x"}]}"#;
+        assert!(matches!(
+            OpenAIService::parse_clip_suggestions(all_bad),
+            Err(LlmError::InvalidResponse(_))
+        ));
+        assert!(OpenAIService::parse_clip_suggestions("No clips stood out, sorry.").is_err());
+    }
+
+    #[test]
+    fn matches_brackets_outside_strings() {
+        assert_eq!(OpenAIService::matching_bracket("[1, [2]] tail"), Some(8));
+        assert_eq!(OpenAIService::matching_bracket(r#"{"a": "}]"} tail"#), Some(11));
+        assert_eq!(OpenAIService::matching_bracket(r#"["say \"]\" now"] tail"#), Some(17));
+        assert_eq!(OpenAIService::matching_bracket("[1, 2"), None);
+        assert_eq!(OpenAIService::matching_bracket("]"), None);
+    }
+
+    #[test]
+    fn extracts_each_json_value_in_text() {
+        let values: Vec<Value> = OpenAIService::json_values_in_text(r#"a {"x": [1]} b [2, "]"] c [oops"#).collect();
+
+        // The inner [1] is found on its own too
+        assert_eq!(values, vec![json!({"x": [1]}), json!([1]), json!([2, "]"])]);
+    }
+
+    fn clip(title: &str, start_secs: u64, end_secs: u64, score: f32) -> ClipSuggestion {
+        ClipSuggestion {
+            title: title.to_string(),
+            start_time: Timestamp::from_millis(start_secs * 1000),
+            end_time: Timestamp::from_millis(end_secs * 1000),
+            score: Some(score),
+        }
+    }
+
+    fn titles(clips: &[ClipSuggestion]) -> Vec<&str> {
+        clips.iter().map(|clip| clip.title.as_str()).collect()
+    }
+
+    #[test]
+    fn treats_mostly_overlapping_clips_as_duplicates() {
+        let a = clip("a", 10, 20, 5.0);
+
+        assert!(OpenAIService::is_duplicate(&a, &clip("b", 14, 30, 5.0)));
+        assert!(OpenAIService::is_duplicate(&a, &clip("inside", 12, 18, 5.0)));
+        // 4s shared of a 10s clip is not more than half
+        assert!(!OpenAIService::is_duplicate(&a, &clip("b", 16, 30, 5.0)));
+        assert!(!OpenAIService::is_duplicate(&a, &clip("touching", 20, 30, 5.0)));
+    }
+
+    #[test]
+    fn drops_duplicates_from_neighbouring_windows() {
+        let candidates = vec![
+            (0, clip("opening", 10, 20, 9.0)),
+            (1, clip("opening again", 12, 21, 7.0)),
+            (1, clip("middle", 60, 70, 6.0)),
+        ];
+
+        let clips = OpenAIService::reduce_candidates(candidates, 5);
+        assert_eq!(titles(&clips), vec!["opening", "middle"]);
+    }
+
+    #[test]
+    fn keeps_the_best_clip_of_every_window_before_filling_by_score() {
+        let candidates = vec![
+            (0, clip("a", 10, 20, 9.0)),
+            (0, clip("b", 30, 40, 8.0)),
+            (1, clip("c", 60, 70, 6.0)),
+            (2, clip("d", 100, 110, 3.0)),
+        ];
+
+        // "b" outscores "d", but window 2 would otherwise go unrepresented
+        let clips = OpenAIService::reduce_candidates(candidates.clone(), 3);
+        assert_eq!(titles(&clips), vec!["a", "c", "d"]);
+
+        // Extra slots go by score, and the result is in timeline order
+        let clips = OpenAIService::reduce_candidates(candidates, 4);
+        assert_eq!(titles(&clips), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn ranks_by_score_when_windows_outnumber_slots() {
+        let candidates = vec![
+            (0, clip("low", 10, 20, 2.0)),
+            (1, clip("high", 60, 70, 9.0)),
+            (2, clip("mid", 100, 110, 5.0)),
+        ];
+
+        let clips = OpenAIService::reduce_candidates(candidates, 2);
+        assert_eq!(titles(&clips), vec!["high", "mid"]);
+    }
 }
@@ -0,0 +1,158 @@
+use crate::models::{Timestamp, VttCue};
+use crate::services::VttParser;
+use regex::Regex;
+
+pub struct SrtParser;
+
+impl SrtParser {
+    /// Parse SubRip content into the same cue structure used for WebVTT
+    pub fn parse_content(content: &str) -> Result<Vec<VttCue>, String> {
+        let content = content.trim_start_matches('\u{feff}');
+
+        // Regex for timestamp line: 00:01:14,500 --> 00:01:18,200
+        // Some tools write '.' instead of ',' or drop the leading hour digit
+        let timestamp_regex = Regex::new(
+            r"^(\d{1,2}:\d{2}:\d{2}[,.]\d{1,3})\s*-->\s*(\d{1,2}:\d{2}:\d{2}[,.]\d{1,3})"
+        ).unwrap();
+
+        let mut cues = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i].trim();
+
+            let Some(captures) = timestamp_regex.captures(line) else {
+                // Sequence numbers and stray lines between blocks
+                i += 1;
+                continue;
+            };
+
+            // The sequence number, if present, is the line right before the timing line
+            let identifier = i
+                .checked_sub(1)
+                .map(|prev| lines[prev].trim())
+                .filter(|prev| !prev.is_empty() && prev.chars().all(|c| c.is_ascii_digit()))
+                .map(str::to_string);
+
+            let (Ok(start), Ok(end)) = (
+                captures[1].parse::<Timestamp>(),
+                captures[2].parse::<Timestamp>(),
+            ) else {
+                println!("⚠️  Skipping SRT block with out-of-range timestamp: {}", line);
+                i += 1;
+                continue;
+            };
+
+            // Collect text lines until the blank line that ends the block
+            let mut text_lines = Vec::new();
+            i += 1;
+
+            while i < lines.len() {
+                let text_line = lines[i].trim();
+                if text_line.is_empty() || timestamp_regex.is_match(text_line) {
+                    break;
+                }
+                text_lines.push(text_line);
+                i += 1;
+            }
+
+            // SRT players understand the same <i>/<b>/<font> style markup, so strip it too
+            let (speaker, text) = VttParser::parse_cue_text(&text_lines.join(" "));
+
+            cues.push(VttCue {
+                identifier,
+                start_timestamp: start,
+                end_timestamp: end,
+                speaker,
+                text,
+                ..Default::default()
+            });
+        }
+
+        if cues.is_empty() {
+            return Err("Invalid SRT file: no subtitle blocks found".to_string());
+        }
+
+        Ok(cues)
+    }
+
+    /// Serialize cues as a SubRip file, numbered from 1
+    ///
+    /// SRT has no voice spans, so speakers are left out.
+    pub fn to_srt(cues: &[VttCue]) -> String {
+        cues.iter()
+            .enumerate()
+            .map(|(index, cue)| {
+                format!(
+                    "{}\n{} --> {}\n{}\n",
+                    index + 1,
+                    cue.start_timestamp.to_string().replace('.', ","),
+                    cue.end_timestamp.to_string().replace('.', ","),
+                    cue.text
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Check whether content looks like SubRip (first block has an SRT timing line)
+    pub fn looks_like_srt(content: &str) -> bool {
+        content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .take(2)
+            .any(|line| line.contains("-->") && line.contains(','))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(value: &str) -> Timestamp {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_sample_srt() {
+        let content = include_str!("../../../tests/fixtures/srt/sample.srt");
+        assert!(SrtParser::looks_like_srt(content));
+        let cues = SrtParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].identifier.as_deref(), Some("1"));
+        assert_eq!((cues[0].start_timestamp, cues[0].end_timestamp), (ts("00:00:01.000"), ts("00:00:04.000")));
+        assert_eq!(cues[0].text, "SubRip uses commas before milliseconds.");
+
+        // Single-digit hours and text wrapped over two lines
+        assert_eq!(cues[1].identifier.as_deref(), Some("2"));
+        assert_eq!((cues[1].start_timestamp, cues[1].end_timestamp), (ts("00:00:04.500"), ts("00:00:08.250")));
+        assert_eq!(cues[1].text, "Some tools drop the leading hour digit and wrap text over two lines.");
+    }
+
+    #[test]
+    fn parses_bom_and_crlf() {
+        let content = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\nFirst\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nSecond\r\n";
+        let cues = SrtParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].identifier.as_deref(), Some("1"));
+        assert_eq!(cues[0].end_timestamp, ts("00:00:02.500"));
+        assert_eq!(cues[1].text, "Second");
+    }
+
+    #[test]
+    fn round_trips_through_to_srt() {
+        let content = include_str!("../../../tests/fixtures/srt/sample.srt");
+        let cues = SrtParser::parse_content(content).unwrap();
+        let reparsed = SrtParser::parse_content(&SrtParser::to_srt(&cues)).unwrap();
+
+        assert_eq!(reparsed.len(), cues.len());
+        for (a, b) in cues.iter().zip(&reparsed) {
+            assert_eq!((a.start_timestamp, a.end_timestamp, &a.text), (b.start_timestamp, b.end_timestamp, &b.text));
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::models::{VttCue, WordTiming};
+use crate::services::{SrtParser, VttParser};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Transcript formats accepted for analysis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    WebVtt,
+    SubRip,
+}
+
+impl TranscriptFormat {
+    /// Detect the format from file content, falling back to the file extension
+    pub fn detect(file_path: &str, content: &str) -> Option<Self> {
+        if content.trim_start_matches('\u{feff}').starts_with("WEBVTT") {
+            return Some(Self::WebVtt);
+        }
+
+        if SrtParser::looks_like_srt(content) {
+            return Some(Self::SubRip);
+        }
+
+        let extension = Path::new(file_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("vtt") => Some(Self::WebVtt),
+            Some("srt") => Some(Self::SubRip),
+            _ => None,
+        }
+    }
+}
+
+pub struct TranscriptLoader;
+
+impl TranscriptLoader {
+    /// Read a transcript file and parse it with the parser matching its format
+    ///
+    /// Word timings saved next to the transcript (see `word_timings_path`)
+    /// are attached to the cues.
+    pub fn load(file_path: &str) -> Result<Vec<VttCue>, String> {
+        let content = fs::read_to_string(file_path)
+            .map_err(|e| format!("Failed to read transcript file: {}", e))?;
+
+        let mut cues = match TranscriptFormat::detect(file_path, &content) {
+            Some(TranscriptFormat::WebVtt) => VttParser::parse_content(&content)?,
+            Some(TranscriptFormat::SubRip) => SrtParser::parse_content(&content)?,
+            None => return Err(
+                "Unrecognized transcript format. Please provide a WebVTT (.vtt) or SubRip (.srt) file."
+                    .to_string(),
+            ),
+        };
+
+        if let Some(words) = Self::load_word_timings(file_path) {
+            println!("Loaded {} word timings", words.len());
+            VttParser::attach_words(&mut cues, words);
+        }
+
+        Ok(cues)
+    }
+
+    /// Word timings file kept beside a transcript: `talk.vtt` -> `talk.words.json`
+    pub fn word_timings_path(transcript_path: &Path) -> PathBuf {
+        transcript_path.with_extension("words.json")
+    }
+
+    /// Save the cues' word timings beside the transcript
+    ///
+    /// Without word timings any older file is removed, so it can't be paired
+    /// with a transcript it doesn't belong to.
+    pub fn save_word_timings(transcript_path: &str, cues: &[VttCue]) -> Result<(), String> {
+        let words: Vec<&WordTiming> = cues.iter().flat_map(|cue| &cue.words).collect();
+        if words.is_empty() {
+            let _ = fs::remove_file(Self::word_timings_path(Path::new(transcript_path)));
+            return Ok(());
+        }
+
+        let json = serde_json::to_string_pretty(&words)
+            .map_err(|e| e.to_string())?;
+        fs::write(Self::word_timings_path(Path::new(transcript_path)), json)
+            .map_err(|e| format!("Failed to save word timings: {}", e))
+    }
+
+    fn load_word_timings(transcript_path: &str) -> Option<Vec<WordTiming>> {
+        let json = fs::read_to_string(Self::word_timings_path(Path::new(transcript_path))).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(words) => Some(words),
+            Err(e) => {
+                println!("⚠️  Ignoring unreadable word timings: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Language recorded in a transcript's header (only WebVTT files carry one)
+    pub fn load_language(file_path: &str) -> Option<String> {
+        let content = fs::read_to_string(file_path).ok()?;
+
+        match TranscriptFormat::detect(file_path, &content) {
+            Some(TranscriptFormat::WebVtt) => VttParser::parse_language(&content),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/../tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    #[test]
+    fn loads_word_timings_beside_the_transcript() {
+        let path = fixture("vtt/word-timings.vtt");
+        let cues = TranscriptLoader::load(&path).unwrap();
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues.iter().map(|cue| cue.words.len()).collect::<Vec<_>>(), vec![9, 5]);
+        assert_eq!(cues[0].words.last().map(|word| word.word.as_str()), Some("cache"));
+        assert_eq!(TranscriptLoader::load_language(&path).as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn detects_format_from_content_then_extension() {
+        let srt = fixture("srt/sample.srt");
+        assert_eq!(TranscriptLoader::load(&srt).unwrap().len(), 2);
+        assert_eq!(TranscriptFormat::detect("talk.txt", "WEBVTT\n"), Some(TranscriptFormat::WebVtt));
+        assert_eq!(TranscriptFormat::detect("talk.srt", ""), Some(TranscriptFormat::SubRip));
+        assert_eq!(TranscriptFormat::detect("talk.txt", "hello"), None);
+    }
+}
@@ -0,0 +1,84 @@
+use crate::models::{TranscriptionBackendKind, TranscriptionOptions, TranscriptionSettings, VttCue};
+use crate::services::whisper::OpenAIWhisperBackend;
+use crate::services::whisper_cpp::WhisperCppBackend;
+use crate::services::{CancelToken, HttpClient};
+use async_trait::async_trait;
+
+/// Receives human-readable progress messages while a backend works
+pub type ProgressCallback<'a> = &'a (dyn Fn(String) + Send + Sync);
+
+/// Audio container a backend wants the extracted soundtrack in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    /// 16 kHz mono MP3, small enough to upload
+    Mp3,
+    /// 16 kHz mono 16-bit PCM, which whisper.cpp reads natively
+    Wav,
+}
+
+impl AudioFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Wav => "wav",
+        }
+    }
+
+    /// FFmpeg output options for this format
+    pub fn ffmpeg_args(self) -> &'static [&'static str] {
+        match self {
+            AudioFormat::Mp3 => &["-acodec", "libmp3lame", "-ar", "16000", "-ac", "1", "-b:a", "64k"],
+            AudioFormat::Wav => &["-acodec", "pcm_s16le", "-ar", "16000", "-ac", "1"],
+        }
+    }
+}
+
+/// Cues produced by a backend, plus the language it heard
+pub struct Transcription {
+    pub cues: Vec<VttCue>,
+    /// Detected (or requested) language code, when the backend reports one
+    pub language: Option<String>,
+}
+
+/// A speech-to-text engine that turns an audio file into timed cues
+#[async_trait]
+pub trait TranscriptionBackend: Send + Sync {
+    /// Human-readable backend description for logs
+    fn describe(&self) -> String;
+
+    /// Format the audio should be extracted in before calling `transcribe`
+    fn audio_format(&self) -> AudioFormat;
+
+    /// Transcribe (or, with `options.translate`, translate to English) an audio file
+    ///
+    /// Stops with an error once `cancel` fires, removing any files it created.
+    async fn transcribe(
+        &self,
+        audio_path: &str,
+        options: &TranscriptionOptions,
+        cancel: &CancelToken,
+        progress_callback: ProgressCallback<'_>,
+    ) -> Result<Transcription, String>;
+}
+
+/// Build the transcription backend selected in settings
+pub fn backend_from_settings(
+    settings: &TranscriptionSettings,
+    openai_api_key: Option<String>,
+    http: HttpClient,
+) -> Result<Box<dyn TranscriptionBackend>, String> {
+    match settings.backend {
+        TranscriptionBackendKind::OpenaiWhisper => {
+            let api_key = openai_api_key
+                .ok_or("No API key configured. Please add your OpenAI API key in Settings.")?;
+            Ok(Box::new(OpenAIWhisperBackend::new(http, api_key, settings.clone())))
+        }
+        TranscriptionBackendKind::WhisperCpp => {
+            let model_path = settings.whisper_cpp_model_path
+                .clone()
+                .filter(|path| !path.trim().is_empty())
+                .ok_or("No Whisper model selected. Choose a GGML model file in Settings to transcribe locally.")?;
+            Ok(Box::new(WhisperCppBackend::new(model_path, settings.whisper_cpp_threads)?))
+        }
+    }
+}
@@ -1,109 +1,422 @@
-use crate::models::VttCue;
+use crate::models::{normalize_language, Timestamp, VttCue, WordTiming};
 use regex::Regex;
-use std::fs;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 pub struct VttParser;
 
 impl VttParser {
-    pub fn parse(file_path: &str) -> Result<Vec<VttCue>, String> {
-        let content = fs::read_to_string(file_path)
-            .map_err(|e| format!("Failed to read VTT file: {}", e))?;
+    /// Parse WebVTT content that has already been read into memory
+    ///
+    /// Follows the WebVTT parsing rules: optional BOM, any line ending,
+    /// hourless timestamps, cue identifiers and settings, and NOTE, STYLE
+    /// and REGION blocks (which are skipped).
+    pub fn parse_content(content: &str) -> Result<Vec<VttCue>, String> {
+        let content = content
+            .trim_start_matches('\u{feff}')
+            .replace("\r\n", "\n")
+            .replace('\r', "\n");
         
-        // Verify WEBVTT header
-        if !content.starts_with("WEBVTT") {
+        // Verify WEBVTT header: the signature must be followed by space, tab or line end
+        let signature_ok = content
+            .strip_prefix("WEBVTT")
+            .map(|rest| rest.is_empty() || rest.starts_with([' ', '\t', '\n']))
+            .unwrap_or(false);
+        if !signature_ok {
             return Err("Invalid VTT file: missing WEBVTT header".to_string());
         }
         
-        let mut cues = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-        let mut i = 0;
-        
-        // Regex for timestamp line: 00:01:14.500 --> 00:01:18.200
-        let timestamp_regex = Regex::new(
-            r"^(\d{2}:\d{2}:\d{2}\.\d{3})\s*-->\s*(\d{2}:\d{2}:\d{2}\.\d{3})"
+        // Regex for timing line: 00:01:14.500 --> 00:01:18.200 align:start
+        // Hours are optional (01:14.500) and may have more than two digits
+        let timing_regex = Regex::new(
+            r"^((?:\d{2,}:)?\d{2}:\d{2}\.\d{3})[ \t]+-->[ \t]+((?:\d{2,}:)?\d{2}:\d{2}\.\d{3})(?:[ \t]+(.*))?$"
         ).unwrap();
         
-        while i < lines.len() {
-            let line = lines[i].trim();
+        let mut cues = Vec::new();
+        
+        // The first block is the header (and any header metadata), so skip it
+        for block in Self::split_blocks(&content).into_iter().skip(1) {
+            if Self::is_block_keyword(block[0], "NOTE")
+                || Self::is_block_keyword(block[0], "STYLE")
+                || Self::is_block_keyword(block[0], "REGION")
+            {
+                continue;
+            }
+            
+            // Timing line is either first, or second after a cue identifier
+            let (identifier, timing_index) = if block[0].contains("-->") {
+                (None, 0)
+            } else if block.len() > 1 && block[1].contains("-->") {
+                (Some(block[0].trim().to_string()), 1)
+            } else {
+                continue;
+            };
+            
+            let Some(captures) = timing_regex.captures(block[timing_index].trim()) else {
+                println!("⚠️  Skipping VTT cue with malformed timing: {}", block[timing_index]);
+                continue;
+            };
+            
+            let (Ok(start), Ok(end)) = (
+                captures[1].parse::<Timestamp>(),
+                captures[2].parse::<Timestamp>(),
+            ) else {
+                println!("⚠️  Skipping VTT cue with out-of-range timestamp: {}", block[timing_index]);
+                continue;
+            };
+            
+            let settings = captures
+                .get(3)
+                .map(|m| Self::parse_cue_settings(m.as_str()))
+                .unwrap_or_default();
+            
+            let raw_text = block[timing_index + 1..]
+                .iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let (speaker, text) = Self::parse_cue_text(&raw_text);
             
-            // Skip empty lines and identifiers
-            if line.is_empty() || line.parse::<u32>().is_ok() {
-                i += 1;
+            cues.push(VttCue {
+                identifier,
+                start_timestamp: start,
+                end_timestamp: end,
+                settings,
+                speaker,
+                text,
+                words: Vec::new(),
+            });
+        }
+        
+        Ok(cues)
+    }
+    
+    /// Split content into blocks separated by blank lines
+    ///
+    /// A line containing "-->" inside a cue ends that cue and starts a new
+    /// block, as the spec requires.
+    fn split_blocks(content: &str) -> Vec<Vec<&str>> {
+        let mut blocks: Vec<Vec<&str>> = Vec::new();
+        let mut current: Vec<&str> = Vec::new();
+        let mut current_has_timing = false;
+        
+        for line in content.lines() {
+            if line.trim().is_empty() {
+                if !current.is_empty() {
+                    blocks.push(std::mem::take(&mut current));
+                }
+                current_has_timing = false;
                 continue;
             }
             
-            // Check if this is a timestamp line
-            if let Some(captures) = timestamp_regex.captures(line) {
-                let start = captures.get(1).unwrap().as_str().to_string();
-                let end = captures.get(2).unwrap().as_str().to_string();
-                
-                // Collect text lines until we hit an empty line or another timestamp
-                let mut text_lines = Vec::new();
-                i += 1;
-                
-                while i < lines.len() {
-                    let text_line = lines[i].trim();
-                    if text_line.is_empty() {
-                        break;
-                    }
-                    // Check if it's another timestamp
-                    if timestamp_regex.is_match(text_line) {
-                        break;
-                    }
-                    text_lines.push(text_line);
-                    i += 1;
+            if line.contains("-->") {
+                if current_has_timing {
+                    blocks.push(std::mem::take(&mut current));
                 }
-                
-                cues.push(VttCue {
-                    start_timestamp: start,
-                    end_timestamp: end,
-                    text: text_lines.join(" "),
-                });
-            } else {
-                i += 1;
+                current_has_timing = true;
             }
+            current.push(line);
         }
         
-        Ok(cues)
+        if !current.is_empty() {
+            blocks.push(current);
+        }
+        
+        blocks
+    }
+    
+    /// Check whether a block starts with a keyword such as NOTE, STYLE or REGION
+    fn is_block_keyword(line: &str, keyword: &str) -> bool {
+        line.strip_prefix(keyword)
+            .map(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+            .unwrap_or(false)
+    }
+    
+    /// Parse cue settings such as `align:start position:10%` into key/value pairs
+    fn parse_cue_settings(settings: &str) -> BTreeMap<String, String> {
+        settings
+            .split_whitespace()
+            .filter_map(|setting| setting.split_once(':'))
+            .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
     }
     
-    /// Convert VTT timestamp (HH:MM:SS.mmm) to FFmpeg format (HH:MM:SS)
-    pub fn vtt_to_ffmpeg_timestamp(vtt_time: &str) -> String {
-        // Remove milliseconds: 00:01:14.500 -> 00:01:14
-        vtt_time.split('.').next().unwrap_or(vtt_time).to_string()
+    /// Read the `Language:` metadata line from a WebVTT header, if present
+    pub fn parse_language(content: &str) -> Option<String> {
+        content
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.trim().is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case("language"))
+            .map(|(_, value)| normalize_language(value))
+            .filter(|language| !language.is_empty())
     }
     
-    /// Find the VTT cue closest to a given timestamp
-    pub fn find_closest_cue<'a>(cues: &'a [VttCue], target_time: &str) -> Option<&'a VttCue> {
-        // Convert target to seconds for comparison
-        let target_seconds = Self::timestamp_to_seconds(target_time)?;
+    /// Split cue text into its speaker (from a `<v Name>` voice span) and plain text
+    ///
+    /// All other inline markup is stripped: `<i>`, `<b>`, `<u>`, `<c.class>`,
+    /// `<ruby>`, `<lang>` and inner timestamps like `<00:00:01.500>`.
+    pub fn parse_cue_text(raw_text: &str) -> (Option<String>, String) {
+        static VOICE_REGEX: OnceLock<Regex> = OnceLock::new();
+        static TAG_REGEX: OnceLock<Regex> = OnceLock::new();
+        
+        let voice_regex = VOICE_REGEX.get_or_init(|| {
+            // <v Speaker Name> or <v.class Speaker Name>
+            Regex::new(r"<v(?:\.[^\s>]*)?[ \t]+([^>]+)>").unwrap()
+        });
+        let tag_regex = TAG_REGEX.get_or_init(|| Regex::new(r"<[^>]*>").unwrap());
+        
+        // A cue can switch voices mid-line; the first voice owns the cue
+        let speaker = voice_regex
+            .captures(raw_text)
+            .map(|captures| captures[1].trim().to_string())
+            .filter(|name| !name.is_empty());
+        
+        let stripped = tag_regex.replace_all(raw_text, "");
+        let text = stripped
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&nbsp;", " ")
+            .replace("&lrm;", "")
+            .replace("&rlm;", "")
+            .replace("&amp;", "&")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        
+        (speaker, text)
+    }
+    
+    /// List distinct speakers in order of first appearance
+    pub fn get_speakers(cues: &[VttCue]) -> Vec<String> {
+        let mut speakers: Vec<String> = Vec::new();
+        for speaker in cues.iter().filter_map(|cue| cue.speaker.as_ref()) {
+            if !speakers.contains(speaker) {
+                speakers.push(speaker.clone());
+            }
+        }
+        speakers
+    }
+    
+    /// Split cues into overlapping time windows for chunked analysis
+    ///
+    /// Each window covers `window` of transcript time and starts `overlap`
+    /// before the previous one ends, so a moment on a boundary is seen whole
+    /// at least once. A zero `window` returns all cues as one window.
+    pub fn window_cues(cues: &[VttCue], window: Duration, overlap: Duration) -> Vec<&[VttCue]> {
+        if window.is_zero() || cues.is_empty() {
+            return vec![cues];
+        }
+        
+        let step = window.saturating_sub(overlap).max(Duration::from_secs(1));
+        let first_at_or_after = |from: usize, time: Timestamp| {
+            cues[from..]
+                .iter()
+                .position(|cue| cue.start_timestamp >= time)
+                .map_or(cues.len(), |offset| from + offset)
+        };
+        
+        let mut windows = Vec::new();
+        let mut start_index = 0;
+        
+        loop {
+            let window_start = cues[start_index].start_timestamp;
+            let end_index = first_at_or_after(start_index, window_start + window);
+            windows.push(&cues[start_index..end_index]);
+            
+            if end_index >= cues.len() {
+                break;
+            }
+            start_index = first_at_or_after(start_index, window_start + step).max(start_index + 1);
+        }
         
+        windows
+    }
+    
+    /// Find the VTT cue whose start is closest to a given timestamp
+    pub fn find_closest_cue(cues: &[VttCue], target_time: Timestamp) -> Option<&VttCue> {
+        cues.iter()
+            .min_by_key(|cue| cue.start_timestamp.abs_diff(target_time))
+    }
+    
+    /// Find the VTT cue whose end is closest to a given timestamp
+    pub fn find_closest_cue_end(cues: &[VttCue], target_time: Timestamp) -> Option<&VttCue> {
         cues.iter()
-            .min_by_key(|cue| {
-                let cue_seconds = Self::timestamp_to_seconds(&cue.start_timestamp).unwrap_or(0);
-                ((cue_seconds as i32) - (target_seconds as i32)).abs()
+            .min_by_key(|cue| cue.end_timestamp.abs_diff(target_time))
+    }
+    
+    /// Move a clip's start and end to the nearest sentence boundaries
+    ///
+    /// Sentences are found from punctuation: one ends on text ending in `.`,
+    /// `!`, `?` or `…`, and the next starts right after. Cues with word
+    /// timings are split at the words, so a sentence can start or end inside
+    /// a cue; other cues only count as a whole. Each edge moves (earlier or
+    /// later) to the closest boundary no more than `max_shift` away, and stays
+    /// put if there is none or the clip would end up empty.
+    pub fn snap_to_sentences(
+        cues: &[VttCue],
+        start: Timestamp,
+        end: Timestamp,
+        max_shift: Duration,
+    ) -> (Timestamp, Timestamp) {
+        // Words where the cue has them, otherwise the whole cue
+        let spans: Vec<(Timestamp, Timestamp, &str)> = cues
+            .iter()
+            .flat_map(|cue| -> Vec<(Timestamp, Timestamp, &str)> {
+                if cue.words.is_empty() {
+                    vec![(cue.start_timestamp, cue.end_timestamp, cue.text.as_str())]
+                } else {
+                    cue.words.iter().map(|word| (word.start, word.end, word.word.as_str())).collect()
+                }
             })
+            .collect();
+        
+        let sentence_starts = spans
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index == 0 || Self::ends_sentence(spans[index - 1].2))
+            .map(|(_, (span_start, _, _))| *span_start);
+        let sentence_ends = spans
+            .iter()
+            .enumerate()
+            .filter(|(index, (_, _, text))| *index == spans.len() - 1 || Self::ends_sentence(text))
+            .map(|(_, (_, span_end, _))| *span_end);
+        
+        // Ties go to the boundary that makes the clip longer (min_by_key keeps the first)
+        let snapped_start = Self::nearest_within(sentence_starts.filter(|boundary| *boundary < end), start, max_shift);
+        let snapped_end = Self::nearest_within(sentence_ends.rev().filter(|boundary| *boundary > snapped_start), end, max_shift);
+        
+        if snapped_end <= snapped_start {
+            return (start, end);
+        }
+        (snapped_start, snapped_end)
+    }
+    
+    /// The boundary closest to `target`, if one is within `max_shift` of it
+    fn nearest_within(boundaries: impl Iterator<Item = Timestamp>, target: Timestamp, max_shift: Duration) -> Timestamp {
+        boundaries
+            .filter(|boundary| boundary.abs_diff(target) <= max_shift)
+            .min_by_key(|boundary| boundary.abs_diff(target))
+            .unwrap_or(target)
+    }
+    
+    /// Whether text finishes a sentence (closing quotes and brackets are skipped)
+    fn ends_sentence(text: &str) -> bool {
+        text.trim_end()
+            .trim_end_matches(['"', '\'', '”', '’', ')', ']', '»'])
+            .ends_with(['.', '!', '?', '…', '。', '！', '？'])
     }
     
-    pub fn timestamp_to_seconds(timestamp: &str) -> Option<u32> {
-        let parts: Vec<&str> = timestamp.split(':').collect();
-        if parts.len() != 3 {
-            return None;
+    /// Serialize cues as a WebVTT file
+    ///
+    /// Keeps identifiers, cue settings and speakers (as `<v>` voice spans), so
+    /// the output parses back to the same cues. The language, if known, goes
+    /// in a `Language:` header line.
+    pub fn to_vtt(cues: &[VttCue], language: Option<&str>) -> String {
+        let mut vtt = String::from("WEBVTT\n");
+        if let Some(language) = language {
+            vtt.push_str(&format!("Language: {}\n", language));
         }
+        vtt.push('\n');
         
-        let hours: u32 = parts[0].parse().ok()?;
-        let minutes: u32 = parts[1].parse().ok()?;
-        let seconds: u32 = parts[2].split('.').next()?.parse().ok()?;
+        for cue in cues {
+            if let Some(identifier) = &cue.identifier {
+                vtt.push_str(identifier);
+                vtt.push('\n');
+            }
+            
+            vtt.push_str(&format!("{} --> {}", cue.start_timestamp, cue.end_timestamp));
+            for (name, value) in &cue.settings {
+                vtt.push_str(&format!(" {}:{}", name, value));
+            }
+            vtt.push('\n');
+            
+            let text = cue.text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            match &cue.speaker {
+                Some(speaker) => vtt.push_str(&format!("<v {}>{}\n\n", speaker, text)),
+                None => vtt.push_str(&format!("{}\n\n", text)),
+            }
+        }
         
-        Some(hours * 3600 + minutes * 60 + seconds)
+        vtt
     }
     
-    /// Get full transcript as plain text (legacy format)
-    pub fn get_full_transcript(cues: &[VttCue]) -> String {
+    /// Start of the word in `cue` closest to `target_time`, if the cue carries word timings
+    pub fn find_closest_word_start(cue: &VttCue, target_time: Timestamp) -> Option<Timestamp> {
+        cue.words
+            .iter()
+            .map(|word| word.start)
+            .min_by_key(|start| start.abs_diff(target_time))
+    }
+    
+    /// End of the word in `cue` closest to `target_time`, if the cue carries word timings
+    pub fn find_closest_word_end(cue: &VttCue, target_time: Timestamp) -> Option<Timestamp> {
+        cue.words
+            .iter()
+            .map(|word| word.end)
+            .min_by_key(|end| end.abs_diff(target_time))
+    }
+    
+    /// Give each cue the words spoken during it
+    ///
+    /// A word belongs to the cue containing its midpoint; words between cues
+    /// go to the nearest one.
+    pub fn attach_words(cues: &mut [VttCue], words: Vec<WordTiming>) {
+        if cues.is_empty() {
+            return;
+        }
+        
+        for word in words {
+            let midpoint = word.start + (word.end - word.start) / 2;
+            let index = cues
+                .iter()
+                .position(|cue| cue.start_timestamp <= midpoint && midpoint < cue.end_timestamp)
+                .unwrap_or_else(|| {
+                    (0..cues.len())
+                        .min_by_key(|&i| {
+                            cues[i].start_timestamp.abs_diff(midpoint)
+                                .min(cues[i].end_timestamp.abs_diff(midpoint))
+                        })
+                        .unwrap_or(0)
+                });
+            cues[index].words.push(word);
+        }
+    }
+    
+    /// Cues heard between `start` and `end`, re-timed so `start` is zero
+    ///
+    /// Cues that run over either edge are cut to fit, and words outside the
+    /// range are dropped. Identifiers are cleared, since they number the
+    /// cues of the whole transcript.
+    pub fn clip_cues(cues: &[VttCue], start: Timestamp, end: Timestamp) -> Vec<VttCue> {
+        let rebase = |time: Timestamp| Timestamp::from_millis((time.min(end) - start).as_millis() as u64);
+        
         cues.iter()
-            .map(|cue| format!("[{}] {}", cue.start_timestamp, cue.text))
-            .collect::<Vec<_>>()
-            .join("\n")
+            .filter(|cue| cue.start_timestamp < end && cue.end_timestamp > start)
+            .map(|cue| VttCue {
+                identifier: None,
+                start_timestamp: rebase(cue.start_timestamp),
+                end_timestamp: rebase(cue.end_timestamp),
+                words: cue.words
+                    .iter()
+                    .filter(|word| word.start < end && word.end > start)
+                    .map(|word| WordTiming {
+                        word: word.word.clone(),
+                        start: rebase(word.start),
+                        end: rebase(word.end),
+                    })
+                    .collect(),
+                ..cue.clone()
+            })
+            .collect()
     }
     
     /// Get formatted VTT with timestamps for AI analysis
@@ -112,15 +425,403 @@ impl VttParser {
         let mut formatted = String::from("WEBVTT - Video Transcript\n\n");
         
         for (index, cue) in cues.iter().enumerate() {
+            // Keep voice spans so the model can tell who is talking
+            let text = match &cue.speaker {
+                Some(speaker) => format!("<v {}>{}", speaker, cue.text),
+                None => cue.text.clone(),
+            };
+            
             formatted.push_str(&format!(
                 "{}\n{} --> {}\n{}\n\n",
                 index + 1,
                 cue.start_timestamp,
                 cue.end_timestamp,
-                cue.text
+                text
             ));
         }
         
         formatted
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(value: &str) -> Timestamp {
+        value.parse().unwrap()
+    }
+
+    fn times(cue: &VttCue) -> (Timestamp, Timestamp) {
+        (cue.start_timestamp, cue.end_timestamp)
+    }
+
+    #[test]
+    fn parses_hourless_timestamps_and_settings() {
+        let content = include_str!("../../../tests/fixtures/vtt/youtube-hourless.vtt");
+        let cues = VttParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 3);
+        assert_eq!(times(&cues[0]), (ts("00:00:00.000"), ts("00:00:03.120")));
+        assert_eq!(times(&cues[2]), (ts("00:00:06.480"), ts("00:00:10.000")));
+        assert_eq!(cues[1].text, "how we ship the new onboarding flow");
+        assert_eq!(cues[0].settings.get("align").map(String::as_str), Some("start"));
+        assert_eq!(cues[0].settings.get("position").map(String::as_str), Some("0%"));
+        assert!(cues.iter().all(|cue| cue.identifier.is_none()));
+        assert_eq!(VttParser::parse_language(content).as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn parses_bom_and_crlf() {
+        let content = include_str!("../../../tests/fixtures/vtt/bom-crlf.vtt");
+        assert!(content.starts_with('\u{feff}'));
+        let cues = VttParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].identifier.as_deref(), Some("1"));
+        assert_eq!(cues[1].identifier.as_deref(), Some("2"));
+        assert_eq!(times(&cues[1]), (ts("00:00:04.500"), ts("00:00:08.250")));
+        assert_eq!(cues[1].text, "and uses Windows line endings.");
+        assert!(cues.iter().all(|cue| !cue.text.contains('\r')));
+    }
+
+    #[test]
+    fn skips_note_style_and_region_blocks() {
+        let content = include_str!("../../../tests/fixtures/vtt/notes-styles-regions.vtt");
+        let cues = VttParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(times(&cues[0]), (ts("00:00:01.000"), ts("00:00:04.000")));
+        assert_eq!(cues[0].text, "Welcome everyone to the keynote.");
+        assert_eq!(cues[0].settings.get("region").map(String::as_str), Some("speaker"));
+        assert_eq!(cues[1].text, "Let's get started.");
+        for skipped in ["is not a cue", "Multi-line notes", "yellow", "regionanchor"] {
+            assert!(cues.iter().all(|cue| !cue.text.contains(skipped)), "{} became a cue", skipped);
+        }
+    }
+
+    #[test]
+    fn parses_identifiers_and_multiline_text() {
+        let content = include_str!("../../../tests/fixtures/vtt/identifiers-multiline.vtt");
+        let cues = VttParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 4);
+        assert_eq!(cues[0].identifier.as_deref(), Some("intro"));
+        assert_eq!(cues[0].text, "Good morning and welcome to the quarterly all-hands.");
+        assert_eq!(cues[0].settings.get("line").map(String::as_str), Some("90%"));
+        assert_eq!(cues[0].settings.get("align").map(String::as_str), Some("center"));
+        assert_eq!(cues[1].identifier.as_deref(), Some("chapter-1-q&a"));
+        assert_eq!(cues[1].text, "First question from the audience.");
+
+        // A timing line inside a cue starts a new cue with no identifier
+        assert_eq!(cues[2].identifier, None);
+        assert_eq!(times(&cues[2]), (ts("00:00:09.000"), ts("00:00:12.000")));
+        assert_eq!(times(&cues[3]), (ts("01:02:03.400"), ts("01:02:07.000")));
+    }
+
+    #[test]
+    fn reads_voice_tags_and_strips_markup() {
+        let content = include_str!("../../../tests/fixtures/vtt/voice-tags.vtt");
+        let cues = VttParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[0].speaker.as_deref(), Some("Host"));
+        assert_eq!(cues[0].text, "Welcome back to the show. Today's guest built the & operator.");
+
+        // A class on the voice span isn't part of the name
+        assert_eq!(cues[1].speaker.as_deref(), Some("Dana Lee"));
+        assert_eq!(cues[1].text, "Thanks for having me. It was not my idea, honestly.");
+
+        // Class spans, inner timestamps and bold markup are dropped
+        assert_eq!(cues[2].speaker, None);
+        assert_eq!(cues[2].text, "Sound check done.");
+
+        assert_eq!(VttParser::get_speakers(&cues), vec!["Host".to_string(), "Dana Lee".to_string()]);
+    }
+
+    fn word_timing_cues() -> Vec<VttCue> {
+        let mut cues = VttParser::parse_content(include_str!("../../../tests/fixtures/vtt/word-timings.vtt")).unwrap();
+        let words = serde_json::from_str(include_str!("../../../tests/fixtures/vtt/word-timings.words.json")).unwrap();
+        VttParser::attach_words(&mut cues, words);
+        cues
+    }
+
+    #[test]
+    fn attaches_words_to_their_cues() {
+        let cues = word_timing_cues();
+
+        assert_eq!(cues[0].words.len(), 9);
+        assert_eq!(cues[1].words.len(), 5);
+        assert_eq!(cues[1].words[0].word, "Latency");
+        assert_eq!((cues[1].words[0].start, cues[1].words[0].end), (ts("00:00:04.650"), ts("00:00:05.210")));
+    }
+
+    #[test]
+    fn snaps_to_words_within_the_given_cue() {
+        let cues = word_timing_cues();
+
+        // The nearest word overall is "cache" in the first cue, but only the second cue's words count
+        assert_eq!(VttParser::find_closest_word_start(&cues[1], ts("00:00:03.000")), Some(ts("00:00:04.650")));
+        assert_eq!(VttParser::find_closest_word_start(&cues[1], ts("00:00:05.650")), Some(ts("00:00:05.600")));
+        assert_eq!(VttParser::find_closest_word_end(&cues[0], ts("00:00:04.200")), Some(ts("00:00:02.710")));
+        assert_eq!(VttParser::find_closest_word_end(&cues[0], ts("00:00:01.900")), Some(ts("00:00:01.820")));
+
+        let without_words = VttCue { words: Vec::new(), ..cues[0].clone() };
+        assert_eq!(VttParser::find_closest_word_start(&without_words, ts("00:00:01.000")), None);
+    }
+
+    fn ms(millis: u64) -> Timestamp {
+        Timestamp::from_millis(millis)
+    }
+
+    fn cue(start: u64, end: u64, text: &str) -> VttCue {
+        VttCue {
+            start_timestamp: ms(start),
+            end_timestamp: ms(end),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Sentences start at 0, 3 and 9 seconds and end at 3, 9 and 12
+    fn sentence_cues() -> Vec<VttCue> {
+        vec![
+            cue(0, 3000, "First sentence here."),
+            cue(3000, 6000, "The second one starts"),
+            cue(6000, 9000, "and ends here."),
+            cue(9000, 12000, "Third sentence"),
+        ]
+    }
+
+    const MAX_SHIFT: Duration = Duration::from_secs(8);
+
+    #[test]
+    fn matches_clip_ends_against_cue_ends() {
+        let cues = sentence_cues();
+
+        // 5.5s is nearest the start of the third cue but the end of the second
+        assert_eq!(VttParser::find_closest_cue(&cues, ms(5500)).unwrap().start_timestamp, ms(6000));
+        assert_eq!(VttParser::find_closest_cue_end(&cues, ms(5500)).unwrap().end_timestamp, ms(6000));
+        assert_eq!(VttParser::find_closest_cue_end(&cues, ms(5500)).unwrap().text, "The second one starts");
+    }
+
+    #[test]
+    fn moves_edges_earlier_or_later() {
+        let cues = sentence_cues();
+
+        // Start moves back to 3, end back to 9
+        assert_eq!(VttParser::snap_to_sentences(&cues, ms(4000), ms(10000), MAX_SHIFT), (ms(3000), ms(9000)));
+        // Start moves on to 9, end on to 12
+        assert_eq!(VttParser::snap_to_sentences(&cues, ms(8000), ms(11500), MAX_SHIFT), (ms(9000), ms(12000)));
+    }
+
+    #[test]
+    fn respects_max_shift() {
+        let cues = sentence_cues();
+
+        assert_eq!(
+            VttParser::snap_to_sentences(&cues, ms(4000), ms(10000), Duration::from_millis(500)),
+            (ms(4000), ms(10000))
+        );
+        assert_eq!(
+            VttParser::snap_to_sentences(&cues, ms(4000), ms(10000), Duration::from_secs(1)),
+            (ms(3000), ms(9000))
+        );
+    }
+
+    #[test]
+    fn breaks_ties_towards_the_longer_clip() {
+        let cues = sentence_cues();
+
+        // 6 is as far from 3 as from 9, and 10.5 as far from 9 as from 12
+        assert_eq!(VttParser::snap_to_sentences(&cues, ms(6000), ms(10500), MAX_SHIFT), (ms(3000), ms(12000)));
+    }
+
+    #[test]
+    fn never_snaps_to_an_empty_clip() {
+        let cues = sentence_cues();
+
+        // Both edges are nearest the boundary at 9, so the end has to find another
+        assert_eq!(VttParser::snap_to_sentences(&cues, ms(8900), ms(9100), MAX_SHIFT), (ms(9000), ms(12000)));
+        assert_eq!(
+            VttParser::snap_to_sentences(&cues, ms(8900), ms(9100), Duration::from_secs(1)),
+            (ms(9000), ms(9100))
+        );
+        // With nothing in reach the edges stay as they were
+        assert_eq!(
+            VttParser::snap_to_sentences(&cues, ms(5000), ms(4500), Duration::ZERO),
+            (ms(5000), ms(4500))
+        );
+    }
+
+    #[test]
+    fn finds_sentence_ends_before_closing_quotes_and_brackets() {
+        for text in ["He said \"stop.\"", "(That was it.)", "Really?]", "C'est fini.»", "Done!”", "Wait…"] {
+            assert!(VttParser::ends_sentence(text), "{}", text);
+        }
+        for text in ["He said \"stop,\"", "(and then)", "no", "3.5 million"] {
+            assert!(!VttParser::ends_sentence(text), "{}", text);
+        }
+    }
+
+    #[test]
+    fn splits_sentences_inside_cues_with_word_timings() {
+        let word = |word: &str, start, end| WordTiming { word: word.to_string(), start: ms(start), end: ms(end) };
+        let cues = vec![
+            VttCue {
+                words: vec![
+                    word("We", 0, 500),
+                    word("shipped", 500, 1000),
+                    word("it.", 1000, 1500),
+                    word("Then", 2000, 2500),
+                    word("we", 2500, 3000),
+                    word("slept.", 3000, 4000),
+                ],
+                ..cue(0, 6000, "We shipped it. Then we slept.")
+            },
+            cue(6000, 9000, "Next part."),
+        ];
+
+        assert_eq!(VttParser::snap_to_sentences(&cues, ms(1800), ms(5000), MAX_SHIFT), (ms(2000), ms(4000)));
+        // The cue without words still only counts as a whole
+        assert_eq!(VttParser::snap_to_sentences(&cues, ms(5000), ms(8000), MAX_SHIFT), (ms(6000), ms(9000)));
+    }
+
+    /// Ten 10-second cues from 0 to 100s
+    fn ten_second_cues() -> Vec<VttCue> {
+        (0..10).map(|i| cue(i * 10_000, (i + 1) * 10_000, &format!("Cue {}", i))).collect()
+    }
+
+    fn window_starts(windows: &[&[VttCue]]) -> Vec<(u64, usize)> {
+        windows
+            .iter()
+            .map(|window| (window[0].start_timestamp.as_duration().as_secs(), window.len()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_short_transcripts_in_one_window() {
+        let cues = ten_second_cues();
+
+        assert_eq!(VttParser::window_cues(&cues, Duration::ZERO, Duration::ZERO).len(), 1);
+        let windows = VttParser::window_cues(&cues, Duration::from_secs(200), Duration::from_secs(30));
+        assert_eq!(window_starts(&windows), vec![(0, 10)]);
+    }
+
+    #[test]
+    fn splits_into_overlapping_windows() {
+        let cues = ten_second_cues();
+        let windows = VttParser::window_cues(&cues, Duration::from_secs(30), Duration::from_secs(10));
+
+        // Each window starts 20s after the previous one, so the cue before its start is seen twice
+        assert_eq!(window_starts(&windows), vec![(0, 3), (20, 3), (40, 3), (60, 3), (80, 2)]);
+        // A cue starting exactly at the window's end belongs to the next window
+        assert_eq!(windows[0].last().unwrap().start_timestamp, ms(20_000));
+        assert_eq!(windows[1][0].start_timestamp, ms(20_000));
+    }
+
+    #[test]
+    fn windows_without_overlap_meet_exactly() {
+        let cues = ten_second_cues();
+        let windows = VttParser::window_cues(&cues, Duration::from_secs(50), Duration::ZERO);
+
+        assert_eq!(window_starts(&windows), vec![(0, 5), (50, 5)]);
+    }
+
+    fn word(word: &str, start: u64, end: u64) -> WordTiming {
+        WordTiming { word: word.to_string(), start: ms(start), end: ms(end) }
+    }
+
+    fn millis(timestamp: Timestamp) -> u64 {
+        timestamp.as_duration().as_millis() as u64
+    }
+
+    fn spans(cues: &[VttCue]) -> Vec<(u64, u64)> {
+        cues.iter().map(|cue| (millis(cue.start_timestamp), millis(cue.end_timestamp))).collect()
+    }
+
+    #[test]
+    fn clips_cues_to_the_range() {
+        let mut cues = vec![
+            cue(0, 4000, "Before the clip."),
+            cue(4000, 12000, "Runs over the start."),
+            cue(12000, 15000, "Inside."),
+            cue(15000, 22000, "Runs over the end."),
+            cue(22000, 25000, "After the clip."),
+        ];
+        cues[2].identifier = Some("3".to_string());
+        cues[2].speaker = Some("Ana".to_string());
+
+        let clipped = VttParser::clip_cues(&cues, ms(10000), ms(20000));
+
+        assert_eq!(spans(&clipped), vec![(0, 2000), (2000, 5000), (5000, 10000)]);
+        assert_eq!(clipped[0].text, "Runs over the start.");
+        assert_eq!(clipped[2].text, "Runs over the end.");
+        // Identifiers number the whole transcript; everything else carries over
+        assert_eq!(clipped[1].identifier, None);
+        assert_eq!(clipped[1].speaker.as_deref(), Some("Ana"));
+    }
+
+    #[test]
+    fn drops_cues_that_only_touch_the_range() {
+        let cues = vec![cue(0, 10000, "Ends at the start."), cue(20000, 25000, "Starts at the end.")];
+
+        assert!(VttParser::clip_cues(&cues, ms(10000), ms(20000)).is_empty());
+    }
+
+    #[test]
+    fn rebases_word_timings() {
+        let mut cues = vec![cue(9000, 13000, "one two three four")];
+        cues[0].words = vec![
+            word("one", 9000, 9800),
+            word("two", 9800, 10600),
+            word("three", 10600, 12200),
+            word("four", 12200, 13000),
+        ];
+
+        let clipped = VttParser::clip_cues(&cues, ms(10000), ms(12500));
+
+        // Words outside the range are dropped, those over an edge are cut to fit
+        let words: Vec<(&str, u64, u64)> = clipped[0].words
+            .iter()
+            .map(|word| (word.word.as_str(), millis(word.start), millis(word.end)))
+            .collect();
+        assert_eq!(words, vec![("two", 0, 600), ("three", 600, 2200), ("four", 2200, 2500)]);
+        assert_eq!(spans(&clipped), vec![(0, 2500)]);
+    }
+
+    #[test]
+    fn writes_vtt_that_parses_back() {
+        let mut cues = vec![
+            cue(0, 2500, "Fish & chips <for two>"),
+            cue(2500, 4000, "Spoken by Ana"),
+            cue(3_600_000, 3_602_000, "An hour in."),
+        ];
+        cues[0].identifier = Some("intro".to_string());
+        cues[0].settings.insert("align".to_string(), "start".to_string());
+        cues[0].settings.insert("line".to_string(), "90%".to_string());
+        cues[1].speaker = Some("Ana".to_string());
+
+        let vtt = VttParser::to_vtt(&cues, Some("en"));
+
+        assert!(vtt.starts_with("WEBVTT\nLanguage: en\n\nintro\n00:00:00.000 --> 00:00:02.500 align:start line:90%\n"));
+        assert!(vtt.contains("Fish &amp; chips &lt;for two&gt;"));
+        assert_eq!(VttParser::parse_language(&vtt).as_deref(), Some("en"));
+        let parsed = VttParser::parse_content(&vtt).unwrap();
+        assert_eq!(spans(&parsed), spans(&cues));
+        for (parsed, cue) in parsed.iter().zip(&cues) {
+            assert_eq!(parsed.identifier, cue.identifier);
+            assert_eq!(parsed.settings, cue.settings);
+            assert_eq!(parsed.speaker, cue.speaker);
+            assert_eq!(parsed.text, cue.text);
+        }
+
+        assert!(VttParser::to_vtt(&[], None).starts_with("WEBVTT\n\n"));
+    }
+
+    #[test]
+    fn rejects_missing_header() {
+        assert!(VttParser::parse_content("00:00.000 --> 00:01.000\nHello").is_err());
+        assert!(VttParser::parse_content("WEBVTTX\n\n00:00.000 --> 00:01.000\nHello").is_err());
+    }
+}
@@ -1,37 +1,101 @@
-use reqwest::Client;
+use crate::models::{
+    language_name, normalize_language, Glossary, Timestamp, TranscriptionOptions, TranscriptionResult,
+    TranscriptionSettings, VttCue, WordTiming,
+};
+use crate::services::audio_chunker::{AudioChunk, AudioChunker};
+use crate::services::transcription::{AudioFormat, ProgressCallback, Transcription, TranscriptionBackend};
+use crate::services::{CancelToken, HttpClient, TranscriptLoader, VttParser};
+use async_trait::async_trait;
+use serde::Deserialize;
 use std::path::Path;
 use std::fs;
-use tauri::api::process::{Command, CommandEvent};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::api::process::{Command, CommandChild, CommandEvent};
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 pub struct WhisperService;
 
 impl WhisperService {
-    /// Extract audio from video and transcribe using OpenAI Whisper
+    /// Extract audio from video and transcribe it with the configured backend
+    ///
+    /// Glossary terms are sent as a vocabulary hint, and with `apply_replacements`
+    /// its known misspellings are corrected before the VTT is written.
     pub async fn transcribe_video(
-        api_key: &str,
+        backend: &dyn TranscriptionBackend,
         video_path: &str,
-        progress_callback: impl Fn(String),
-    ) -> Result<String, String> {
+        options: &TranscriptionOptions,
+        glossary: &Glossary,
+        apply_replacements: bool,
+        cancel: &CancelToken,
+        progress_callback: ProgressCallback<'_>,
+    ) -> Result<TranscriptionResult, String> {
+        println!("Transcription backend: {}", backend.describe());
+        println!(
+            "Language: {}, translate: {}",
+            options.language.as_deref().unwrap_or("auto-detect"),
+            options.translate
+        );
+        if !glossary.is_empty() {
+            println!(
+                "Glossary: {} terms, {} replacements",
+                glossary.terms.len(),
+                glossary.replacements.len()
+            );
+        }
+        
         // Step 1: Extract audio from video using FFmpeg
         progress_callback("Extracting audio from video...".to_string());
-        let audio_path = Self::extract_audio(video_path).await?;
+        let audio_path = Self::extract_audio(video_path, backend.audio_format(), cancel).await?;
         
-        // Step 2: Transcribe audio using Whisper API
-        progress_callback("Transcribing audio with Whisper AI...".to_string());
-        let vtt_content = Self::transcribe_audio(api_key, &audio_path).await?;
+        // Step 2: Transcribe audio
+        let options = TranscriptionOptions {
+            prompt: glossary.prompt(),
+            ..options.clone()
+        };
+        let result = backend.transcribe(&audio_path, &options, cancel, progress_callback).await;
+        
+        // Step 3: Clean up temporary audio file
+        let _ = fs::remove_file(&audio_path);
         
-        // Step 3: Save VTT file
+        // Step 4: Enforce known spellings
+        let mut transcription = result?;
+        if apply_replacements && !glossary.replacements.is_empty() {
+            for cue in &mut transcription.cues {
+                cue.text = glossary.apply(&cue.text);
+                cue.words = glossary.apply_words(&cue.words);
+            }
+            println!("Applied {} glossary replacements", glossary.replacements.len());
+        }
+        
+        // Step 5: Save VTT file, recording the language in its header
+        let language = if options.translate {
+            Some("en".to_string())
+        } else {
+            transcription.language.or_else(|| options.language.as_deref().map(normalize_language))
+        };
+        let vtt_content = VttParser::to_vtt(&transcription.cues, language.as_deref());
         let vtt_path = Self::save_vtt_file(video_path, &vtt_content)?;
+        TranscriptLoader::save_word_timings(&vtt_path, &transcription.cues)?;
         
-        // Step 4: Clean up temporary audio file
-        let _ = fs::remove_file(&audio_path);
+        match &language {
+            Some(code) => progress_callback(format!("Transcript generated ({})!", language_name(code))),
+            None => progress_callback("Transcript generated successfully!".to_string()),
+        }
         
-        progress_callback("Transcript generated successfully!".to_string());
-        Ok(vtt_path)
+        Ok(TranscriptionResult {
+            vtt_path,
+            language,
+            translated: options.translate,
+        })
     }
     
-    /// Extract audio from video to temporary MP3 file
-    async fn extract_audio(video_path: &str) -> Result<String, String> {
+    /// Extract audio from video to a temporary file in the backend's format
+    ///
+    /// The partial file is removed if extraction fails or is cancelled.
+    async fn extract_audio(video_path: &str, format: AudioFormat, cancel: &CancelToken) -> Result<String, String> {
         println!("=== Whisper: Audio Extraction ===");
         println!("Video path: {}", video_path);
         
@@ -40,30 +104,24 @@ impl WhisperService {
             .parent()
             .ok_or("Invalid video path")?
             .join(format!(
-                "{}_temp_audio.mp3",
+                "{}_temp_audio.{}",
                 video_path_obj.file_stem()
                     .and_then(|s| s.to_str())
-                    .ok_or("Invalid filename")?
+                    .ok_or("Invalid filename")?,
+                format.extension()
             ));
         
         let audio_path_str = audio_path.to_str().ok_or("Invalid path")?;
         println!("Audio output: {}", audio_path_str);
         
-        let args = vec![
-            "-i", video_path,
-            "-vn",
-            "-acodec", "libmp3lame",
-            "-ar", "16000",
-            "-ac", "1",
-            "-b:a", "64k",
-            "-y",
-            audio_path_str,
-        ];
+        let mut args = vec!["-i", video_path, "-vn"];
+        args.extend_from_slice(format.ffmpeg_args());
+        args.extend(["-y", audio_path_str]);
         
         println!("FFmpeg audio extraction args: {:?}", args);
         
         // Use FFmpeg to extract audio
-        let (mut rx, _child) = Command::new_sidecar("ffmpeg")
+        let (mut rx, child) = Command::new_sidecar("ffmpeg")
             .map_err(|e| {
                 let err_msg = format!("Failed to find FFmpeg: {}", e);
                 println!("ERROR: {}", err_msg);
@@ -80,7 +138,24 @@ impl WhisperService {
         println!("FFmpeg audio extraction spawned successfully");
         
         // Wait for completion
-        while let Some(event) = rx.recv().await {
+        let result = Self::wait_for_extraction(&mut rx, child, cancel).await;
+        if result.is_err() {
+            let _ = fs::remove_file(&audio_path);
+        }
+        result?;
+        
+        Ok(audio_path_str.to_string())
+    }
+    
+    /// Follow the audio extraction FFmpeg run until it exits
+    async fn wait_for_extraction(
+        rx: &mut Receiver<CommandEvent>,
+        child: CommandChild,
+        cancel: &CancelToken,
+    ) -> Result<(), String> {
+        let mut child = Some(child);
+        
+        while let Some(event) = cancel.next_event(rx, &mut child).await? {
             match event {
                 CommandEvent::Stdout(line) => {
                     println!("Audio extraction STDOUT: {}", line);
@@ -105,13 +180,231 @@ impl WhisperService {
             }
         }
         
-        Ok(audio_path_str.to_string())
+        Ok(())
+    }
+    
+    /// Save VTT content to file next to video
+    fn save_vtt_file(video_path: &str, vtt_content: &str) -> Result<String, String> {
+        let video_path_obj = Path::new(video_path);
+        let vtt_path = video_path_obj
+            .parent()
+            .ok_or("Invalid video path")?
+            .join(format!(
+                "{}.vtt",
+                video_path_obj.file_stem()
+                    .and_then(|s| s.to_str())
+                    .ok_or("Invalid filename")?
+            ));
+        
+        fs::write(&vtt_path, vtt_content)
+            .map_err(|e| format!("Failed to save VTT file: {}", e))?;
+        
+        Ok(vtt_path.to_str().ok_or("Invalid path")?.to_string())
+    }
+}
+
+/// Transcription through OpenAI's hosted `whisper-1` model
+///
+/// Audio over one chunk long is split on silences and the pieces are
+/// uploaded in parallel, keeping each request under the 25MB limit.
+pub struct OpenAIWhisperBackend {
+    http: HttpClient,
+    api_key: String,
+    settings: TranscriptionSettings,
+}
+
+#[async_trait]
+impl TranscriptionBackend for OpenAIWhisperBackend {
+    fn describe(&self) -> String {
+        "OpenAI Whisper (whisper-1)".to_string()
+    }
+    
+    fn audio_format(&self) -> AudioFormat {
+        AudioFormat::Mp3
+    }
+    
+    /// Transcribe audio of any length, splitting it to stay under the upload limit
+    async fn transcribe(
+        &self,
+        audio_path: &str,
+        options: &TranscriptionOptions,
+        cancel: &CancelToken,
+        progress_callback: ProgressCallback<'_>,
+    ) -> Result<Transcription, String> {
+        progress_callback("Analyzing audio...".to_string());
+        let (duration, silences) = AudioChunker::analyze(audio_path, cancel).await?;
+        
+        let chunks = AudioChunker::plan(
+            audio_path,
+            duration,
+            &silences,
+            Duration::from_secs(self.settings.chunk_minutes as u64 * 60),
+            Duration::from_secs(self.settings.chunk_overlap_seconds as u64),
+        );
+        
+        if chunks.len() == 1 {
+            progress_callback("Transcribing audio with Whisper AI...".to_string());
+            return Self::transcribe_audio(&self.http, &self.api_key, audio_path, options).await;
+        }
+        
+        println!("=== Whisper: Transcribing {} chunks ===", chunks.len());
+        let result = self.transcribe_chunks(audio_path, &chunks, options, cancel, progress_callback).await;
+        
+        for chunk in &chunks {
+            let _ = fs::remove_file(&chunk.path);
+        }
+        
+        Ok(Self::stitch_chunks(&chunks, result?))
+    }
+}
+
+impl OpenAIWhisperBackend {
+    pub fn new(http: HttpClient, api_key: String, settings: TranscriptionSettings) -> Self {
+        Self { http, api_key, settings }
+    }
+    
+    /// Transcribe every chunk, a few at a time, returning results in chunk order
+    async fn transcribe_chunks(
+        &self,
+        audio_path: &str,
+        chunks: &[AudioChunk],
+        options: &TranscriptionOptions,
+        cancel: &CancelToken,
+        progress_callback: ProgressCallback<'_>,
+    ) -> Result<Vec<Transcription>, String> {
+        let total = chunks.len();
+        let semaphore = Arc::new(Semaphore::new(self.settings.max_concurrent_chunks.max(1)));
+        let mut tasks = JoinSet::new();
+        
+        for chunk in chunks {
+            progress_callback(format!("Preparing audio chunk {} of {}...", chunk.index + 1, total));
+            AudioChunker::extract(audio_path, chunk, cancel).await?;
+            
+            // Uploads start as soon as their chunk is ready
+            let semaphore = semaphore.clone();
+            let http = self.http.clone();
+            let api_key = self.api_key.clone();
+            let chunk = chunk.clone();
+            let options = options.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await
+                    .map_err(|e| e.to_string())?;
+                let transcription = Self::transcribe_audio(&http, &api_key, &chunk.path, &options).await
+                    .map_err(|e| format!("Chunk {}: {}", chunk.index + 1, e))?;
+                Ok::<_, String>((chunk.index, transcription))
+            });
+        }
+        
+        // Dropping the JoinSet on error aborts the chunks still in flight
+        let mut transcriptions: Vec<Option<Transcription>> = (0..total).map(|_| None).collect();
+        let mut completed = 0;
+        
+        while let Some(joined) = tasks.join_next().await {
+            let (index, transcription) = joined.map_err(|e| format!("Transcription task failed: {}", e))??;
+            transcriptions[index] = Some(transcription);
+            completed += 1;
+            progress_callback(format!("Transcribed {} of {} chunks...", completed, total));
+        }
+        
+        Ok(transcriptions.into_iter().flatten().collect())
+    }
+    
+    /// Merge per-chunk transcripts into one on the original timeline
+    ///
+    /// Each chunk keeps the cues whose midpoint falls in its own range, then
+    /// words repeated across a cut (heard in both chunks' overlap) are dropped.
+    /// The language is whichever most chunks detected.
+    fn stitch_chunks(chunks: &[AudioChunk], transcriptions: Vec<Transcription>) -> Transcription {
+        let mut stitched: Vec<VttCue> = Vec::new();
+        let mut languages: Vec<String> = Vec::new();
+        
+        for (chunk, transcription) in chunks.iter().zip(transcriptions) {
+            languages.extend(transcription.language);
+            let offset = chunk.audio_start.as_duration();
+            let mut first_in_chunk = true;
+            
+            for mut cue in transcription.cues {
+                cue.start_timestamp = cue.start_timestamp + offset;
+                cue.end_timestamp = cue.end_timestamp + offset;
+                cue.identifier = None;
+                for word in &mut cue.words {
+                    word.start = word.start + offset;
+                    word.end = word.end + offset;
+                }
+                
+                let midpoint = cue.start_timestamp + (cue.end_timestamp - cue.start_timestamp) / 2;
+                if midpoint < chunk.keep_start || midpoint >= chunk.keep_end {
+                    continue;
+                }
+                
+                if first_in_chunk {
+                    first_in_chunk = false;
+                    if let Some(previous) = stitched.last() {
+                        let (text, repeated) = Self::strip_repeated_words(&previous.text, &cue.text);
+                        cue.text = text;
+                        if repeated > 0 {
+                            cue.words.drain(..repeated.min(cue.words.len()));
+                            if let Some(first_word) = cue.words.first() {
+                                cue.start_timestamp = first_word.start;
+                            }
+                        }
+                        cue.start_timestamp = cue.start_timestamp.max(previous.end_timestamp);
+                    }
+                }
+                
+                if !cue.text.is_empty() && cue.end_timestamp > cue.start_timestamp {
+                    stitched.push(cue);
+                }
+            }
+        }
+        
+        println!("✅ Stitched {} cues from {} chunks", stitched.len(), chunks.len());
+        
+        let language = languages
+            .iter()
+            .max_by_key(|language| languages.iter().filter(|other| other == language).count())
+            .cloned();
+        
+        Transcription {
+            cues: stitched,
+            language,
+        }
     }
     
-    /// Transcribe audio file using OpenAI Whisper API
-    async fn transcribe_audio(api_key: &str, audio_path: &str) -> Result<String, String> {
-        let client = Client::new();
+    /// Drop the longest run of words at the start of `text` that repeats the end of `previous`
+    ///
+    /// Returns the remaining text and how many words were dropped.
+    fn strip_repeated_words(previous: &str, text: &str) -> (String, usize) {
+        let normalize = |word: &str| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        };
+        let previous_words: Vec<String> = previous.split_whitespace().map(normalize).collect();
+        let words: Vec<&str> = text.split_whitespace().collect();
         
+        // A single matching word is too often a coincidence ("the", "and")
+        let repeated = (2..=previous_words.len().min(words.len()))
+            .rev()
+            .find(|&count| {
+                previous_words[previous_words.len() - count..]
+                    .iter()
+                    .zip(&words[..count])
+                    .all(|(a, b)| !a.is_empty() && *a == normalize(b))
+            })
+            .unwrap_or(0);
+        
+        (words[repeated..].join(" "), repeated)
+    }
+    
+    /// Transcribe (or translate) an audio file using OpenAI Whisper API
+    async fn transcribe_audio(
+        http: &HttpClient,
+        api_key: &str,
+        audio_path: &str,
+        options: &TranscriptionOptions,
+    ) -> Result<Transcription, String> {
         // Read audio file
         let audio_data = fs::read(audio_path)
             .map_err(|e| format!("Failed to read audio file: {}", e))?;
@@ -121,58 +414,255 @@ impl WhisperService {
             return Err("Audio file too large (max 25MB). Try a shorter video.".to_string());
         }
         
-        // Create multipart form
-        let form = reqwest::multipart::Form::new()
-            .part(
-                "file",
-                reqwest::multipart::Part::bytes(audio_data)
-                    .file_name("audio.mp3")
-                    .mime_str("audio/mpeg")
-                    .map_err(|e| format!("Failed to create form part: {}", e))?,
-            )
-            .text("model", "whisper-1")
-            .text("response_format", "vtt")
-            .text("language", "en"); // Can be made configurable
-        
-        let response = client
-            .post("https://api.openai.com/v1/audio/transcriptions")
-            .header("Authorization", format!("Bearer {}", api_key))
-            .multipart(form)
-            .send()
+        // Translations always come out in English and take no language hint
+        let endpoint = if options.translate { "translations" } else { "transcriptions" };
+        let language = options.language.as_deref()
+            .filter(|_| !options.translate)
+            .map(normalize_language);
+        
+        // Multipart forms are consumed by sending, so build a fresh one per attempt
+        let response = http
+            .send_with_retry(|client| {
+                let mut form = reqwest::multipart::Form::new()
+                    .part(
+                        "file",
+                        reqwest::multipart::Part::bytes(audio_data.clone())
+                            .file_name("audio.mp3")
+                            .mime_str("audio/mpeg")
+                            .map_err(|e| format!("Failed to create form part: {}", e))?,
+                    )
+                    .text("model", "whisper-1")
+                    // verbose_json reports the detected language alongside timed segments
+                    .text("response_format", "verbose_json");
+                if let Some(language) = &language {
+                    form = form.text("language", language.clone());
+                }
+                if let Some(prompt) = &options.prompt {
+                    form = form.text("prompt", prompt.clone());
+                }
+                // Word timings are only offered for transcriptions
+                if !options.translate {
+                    form = form
+                        .text("timestamp_granularities[]", "word")
+                        .text("timestamp_granularities[]", "segment");
+                }
+                
+                Ok(client
+                    .post(format!("https://api.openai.com/v1/audio/{}", endpoint))
+                    .header("Authorization", format!("Bearer {}", api_key))
+                    .multipart(form))
+            })
             .await
             .map_err(|e| format!("Whisper API request failed: {}", e))?;
         
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(format!("Whisper API error ({}): {}", status, error_text));
+        if !response.status.is_success() {
+            return Err(format!("Whisper API error ({}): {}", response.status, response.body));
         }
         
-        // Get VTT content
-        let vtt_content = response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read response: {}", e))?;
+        let verbose: VerboseTranscription = serde_json::from_str(&response.body)
+            .map_err(|e| format!("Failed to parse Whisper response: {}", e))?;
         
-        Ok(vtt_content)
-    }
-    
-    /// Save VTT content to file next to video
-    fn save_vtt_file(video_path: &str, vtt_content: &str) -> Result<String, String> {
-        let video_path_obj = Path::new(video_path);
-        let vtt_path = video_path_obj
-            .parent()
-            .ok_or("Invalid video path")?
-            .join(format!(
-                "{}.vtt",
-                video_path_obj.file_stem()
-                    .and_then(|s| s.to_str())
-                    .ok_or("Invalid filename")?
-            ));
+        let mut cues: Vec<VttCue> = verbose.segments
+            .into_iter()
+            .map(|segment| VttCue {
+                start_timestamp: Timestamp::from_secs_f64(segment.start),
+                end_timestamp: Timestamp::from_secs_f64(segment.end),
+                text: segment.text.split_whitespace().collect::<Vec<_>>().join(" "),
+                ..Default::default()
+            })
+            .filter(|cue| !cue.text.is_empty())
+            .collect();
         
-        fs::write(&vtt_path, vtt_content)
-            .map_err(|e| format!("Failed to save VTT file: {}", e))?;
+        let words = verbose.words
+            .into_iter()
+            .map(|word| WordTiming {
+                word: word.word.trim().to_string(),
+                start: Timestamp::from_secs_f64(word.start),
+                end: Timestamp::from_secs_f64(word.end),
+            })
+            .filter(|word| !word.word.is_empty())
+            .collect();
+        VttParser::attach_words(&mut cues, words);
         
-        Ok(vtt_path.to_str().ok_or("Invalid path")?.to_string())
+        Ok(Transcription {
+            cues,
+            language: if options.translate {
+                Some("en".to_string())
+            } else {
+                language.or_else(|| verbose.language.as_deref().map(normalize_language))
+            },
+        })
+    }
+}
+
+// Whisper `verbose_json` response (only the fields we use)
+#[derive(Debug, Deserialize)]
+struct VerboseTranscription {
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    #[serde(default)]
+    words: Vec<VerboseWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    start: f64,
+    end: f64,
+    text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Timestamp {
+        Timestamp::from_millis(millis)
+    }
+
+    fn chunk(index: usize, audio: (u64, u64), keep: (u64, u64)) -> AudioChunk {
+        AudioChunk {
+            index,
+            audio_start: ms(audio.0),
+            audio_end: ms(audio.1),
+            keep_start: ms(keep.0),
+            keep_end: ms(keep.1),
+            path: format!("chunk{}.mp3", index + 1),
+        }
+    }
+
+    fn cue(start: u64, end: u64, text: &str, words: &[(&str, u64, u64)]) -> VttCue {
+        VttCue {
+            identifier: Some("1".to_string()),
+            start_timestamp: ms(start),
+            end_timestamp: ms(end),
+            text: text.to_string(),
+            words: words
+                .iter()
+                .map(|&(word, start, end)| WordTiming { word: word.to_string(), start: ms(start), end: ms(end) })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn transcription(cues: Vec<VttCue>, language: Option<&str>) -> Transcription {
+        Transcription { cues, language: language.map(str::to_string) }
+    }
+
+    #[test]
+    fn stitches_chunks_onto_one_timeline() {
+        // Cut at 60s with 2s of overlap either side
+        let chunks = [chunk(0, (0, 62_000), (0, 60_000)), chunk(1, (58_000, 120_000), (60_000, 120_000))];
+        let first = transcription(
+            vec![
+                cue(0, 3_000, "Hello there.", &[]),
+                cue(55_000, 61_000, "we talked about caching today", &[]),
+                // Midpoint past the cut: the next chunk owns it
+                cue(61_000, 62_000, "and then", &[]),
+            ],
+            Some("en"),
+        );
+        let second = transcription(
+            vec![
+                // Midpoint before the cut: already heard in the first chunk
+                cue(0, 1_500, "caching today", &[]),
+                cue(
+                    1_500,
+                    6_000,
+                    "caching today and more stuff",
+                    &[("caching", 1_500, 2_000), ("today", 2_000, 2_500), ("and", 3_000, 3_300), ("more", 3_300, 3_800), ("stuff", 3_800, 4_500)],
+                ),
+                cue(50_000, 55_000, "Bye.", &[]),
+            ],
+            Some("en"),
+        );
+
+        let stitched = OpenAIWhisperBackend::stitch_chunks(&chunks, vec![first, second]);
+        let cues: Vec<(u64, u64, &str)> = stitched
+            .cues
+            .iter()
+            .map(|cue| (cue.start_timestamp.as_duration().as_millis() as u64, cue.end_timestamp.as_duration().as_millis() as u64, cue.text.as_str()))
+            .collect();
+
+        assert_eq!(
+            cues,
+            vec![
+                (0, 3_000, "Hello there."),
+                (55_000, 61_000, "we talked about caching today"),
+                // "caching today" is heard in both chunks, so it starts at "and" (3s + 58s)
+                (61_000, 64_000, "and more stuff"),
+                (108_000, 113_000, "Bye."),
+            ]
+        );
+
+        // Words are moved onto the full timeline along with the cue
+        let words: Vec<(&str, Timestamp)> = stitched.cues[2].words.iter().map(|word| (word.word.as_str(), word.start)).collect();
+        assert_eq!(words, vec![("and", ms(61_000)), ("more", ms(61_300)), ("stuff", ms(61_800))]);
+        assert!(stitched.cues.iter().all(|cue| cue.identifier.is_none()));
+        assert_eq!(stitched.language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn drops_cues_left_empty_by_de_duplication() {
+        let chunks = [chunk(0, (0, 32_000), (0, 30_000)), chunk(1, (28_000, 60_000), (30_000, 60_000))];
+        let first = transcription(vec![cue(20_000, 31_000, "see you next week", &[])], None);
+        let second = transcription(vec![cue(1_000, 4_000, "next week", &[]), cue(4_000, 8_000, "Welcome back.", &[])], None);
+
+        let stitched = OpenAIWhisperBackend::stitch_chunks(&chunks, vec![first, second]);
+        let texts: Vec<&str> = stitched.cues.iter().map(|cue| cue.text.as_str()).collect();
+
+        assert_eq!(texts, vec!["see you next week", "Welcome back."]);
+        assert_eq!(stitched.language, None);
+    }
+
+    #[test]
+    fn takes_the_language_most_chunks_detected() {
+        let chunks = [chunk(0, (0, 10), (0, 10)), chunk(1, (10, 20), (10, 20)), chunk(2, (20, 30), (20, 30))];
+        let transcriptions = vec![
+            transcription(Vec::new(), Some("de")),
+            transcription(Vec::new(), Some("en")),
+            transcription(Vec::new(), Some("de")),
+        ];
+
+        assert_eq!(OpenAIWhisperBackend::stitch_chunks(&chunks, transcriptions).language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn strips_words_repeated_across_a_cut() {
+        assert_eq!(
+            OpenAIWhisperBackend::strip_repeated_words("We shipped it.", "Shipped it, then slept."),
+            ("then slept.".to_string(), 2)
+        );
+        // The longest repeat wins
+        assert_eq!(
+            OpenAIWhisperBackend::strip_repeated_words("one two one two", "one two one two three"),
+            ("three".to_string(), 4)
+        );
+        assert_eq!(
+            OpenAIWhisperBackend::strip_repeated_words("and then the end", "the end"),
+            (String::new(), 2)
+        );
+    }
+
+    #[test]
+    fn keeps_single_and_non_leading_matches() {
+        // One shared word is usually a coincidence
+        assert_eq!(
+            OpenAIWhisperBackend::strip_repeated_words("over to the", "the next slide"),
+            ("the next slide".to_string(), 0)
+        );
+        assert_eq!(
+            OpenAIWhisperBackend::strip_repeated_words("next slide please", "so next slide please"),
+            ("so next slide please".to_string(), 0)
+        );
+        assert_eq!(OpenAIWhisperBackend::strip_repeated_words("", "hello world"), ("hello world".to_string(), 0));
     }
 }
@@ -0,0 +1,236 @@
+use crate::models::{normalize_language, TranscriptionOptions};
+use crate::services::transcription::{AudioFormat, ProgressCallback, Transcription, TranscriptionBackend};
+use crate::services::{CancelToken, VttParser};
+use async_trait::async_trait;
+use regex::Regex;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use tauri::api::process::{Command, CommandEvent};
+
+/// Offline transcription with the bundled whisper.cpp sidecar
+///
+/// Runs entirely on the CPU with a user-supplied GGML model (e.g.
+/// `ggml-base.en.bin`), so no audio leaves the machine.
+pub struct WhisperCppBackend {
+    model_path: String,
+    threads: u32,
+}
+
+impl WhisperCppBackend {
+    pub fn new(model_path: String, threads: u32) -> Result<Self, String> {
+        if !Path::new(&model_path).is_file() {
+            return Err(format!("Whisper model file not found: {}", model_path));
+        }
+
+        // 0 = one thread per core, up to 8 (more rarely helps on CPU)
+        let threads = if threads == 0 {
+            std::thread::available_parallelism()
+                .map(|n| n.get().min(8) as u32)
+                .unwrap_or(4)
+        } else {
+            threads
+        };
+
+        Ok(Self { model_path, threads })
+    }
+
+    /// whisper.cpp arguments to transcribe `audio_path` into `{output_base}.vtt`
+    fn args(&self, audio_path: &str, output_base: &str, options: &TranscriptionOptions) -> Vec<String> {
+        let language = options.language.as_deref()
+            .map(normalize_language)
+            .unwrap_or_else(|| "auto".to_string());
+
+        let mut args: Vec<String> = [
+            "-m", &self.model_path,
+            "-f", audio_path,
+            "-t", &self.threads.to_string(),
+            "-l", &language,
+            "-ovtt",
+            "-of", output_base,
+            "-pp",
+        ].iter().map(|arg| arg.to_string()).collect();
+        if options.translate {
+            args.push("-tr".to_string());
+        }
+        if let Some(prompt) = &options.prompt {
+            args.extend(["--prompt".to_string(), prompt.clone()]);
+        }
+        args
+    }
+
+    /// Percentage from a progress line: "whisper_print_progress_callback: progress =  40%"
+    fn parse_progress(line: &str) -> Option<u32> {
+        static PROGRESS_REGEX: OnceLock<Regex> = OnceLock::new();
+        let regex = PROGRESS_REGEX.get_or_init(|| Regex::new(r"progress\s*=\s*(\d+)%").unwrap());
+        regex.captures(line)?[1].parse().ok()
+    }
+
+    /// Language from "whisper_full_with_state: auto-detected language: de (p = 0.974)"
+    fn parse_detected_language(line: &str) -> Option<String> {
+        static LANGUAGE_REGEX: OnceLock<Regex> = OnceLock::new();
+        let regex = LANGUAGE_REGEX.get_or_init(|| Regex::new(r"auto-detected language:\s*([a-z]+)").unwrap());
+        regex.captures(line).map(|captures| captures[1].to_string())
+    }
+}
+
+#[async_trait]
+impl TranscriptionBackend for WhisperCppBackend {
+    fn describe(&self) -> String {
+        format!("whisper.cpp ({}, {} threads)", self.model_path, self.threads)
+    }
+
+    fn audio_format(&self) -> AudioFormat {
+        AudioFormat::Wav
+    }
+
+    async fn transcribe(
+        &self,
+        audio_path: &str,
+        options: &TranscriptionOptions,
+        cancel: &CancelToken,
+        progress_callback: ProgressCallback<'_>,
+    ) -> Result<Transcription, String> {
+        println!("=== whisper.cpp Transcription ===");
+        println!("Model: {}", self.model_path);
+        println!("Audio: {}", audio_path);
+
+        // whisper.cpp appends ".vtt" to the output base name
+        let output_base = Path::new(audio_path).with_extension("");
+        let output_base_str = output_base.to_str().ok_or("Invalid path")?;
+        let vtt_path = format!("{}.vtt", output_base_str);
+        let args = self.args(audio_path, output_base_str, options);
+
+        println!("whisper.cpp args: {:?}", args);
+        progress_callback("Transcribing audio locally...".to_string());
+
+        let (mut rx, child) = Command::new_sidecar("whisper-cpp")
+            .map_err(|e| format!("Failed to find whisper.cpp: {}", e))?
+            .args(&args)
+            .spawn()
+            .map_err(|e| format!("Failed to spawn whisper.cpp: {}", e))?;
+
+        let mut detected_language = None;
+        let mut stderr_lines = Vec::new();
+        let mut child = Some(child);
+
+        loop {
+            let event = match cancel.next_event(&mut rx, &mut child).await {
+                Ok(Some(event)) => event,
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = fs::remove_file(&vtt_path);
+                    return Err(e);
+                }
+            };
+
+            match event {
+                CommandEvent::Stderr(line) => {
+                    if let Some(percent) = Self::parse_progress(&line) {
+                        progress_callback(format!("Transcribing audio locally... {}%", percent));
+                    }
+                    if let Some(language) = Self::parse_detected_language(&line) {
+                        detected_language = Some(language);
+                    }
+                    stderr_lines.push(line);
+                }
+                CommandEvent::Error(error) => {
+                    return Err(format!("whisper.cpp error: {}", error));
+                }
+                CommandEvent::Terminated(payload) => {
+                    println!("whisper.cpp terminated: code={:?}, signal={:?}", payload.code, payload.signal);
+                    if payload.code != Some(0) {
+                        println!("=== FULL STDERR ===");
+                        for line in &stderr_lines {
+                            println!("{}", line);
+                        }
+                        println!("=== END STDERR ===");
+                        let _ = fs::remove_file(&vtt_path);
+                        return Err(format!("whisper.cpp failed with code: {:?}", payload.code));
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        let vtt_content = fs::read_to_string(&vtt_path)
+            .map_err(|e| format!("Failed to read whisper.cpp output: {}", e))?;
+        let _ = fs::remove_file(&vtt_path);
+
+        let language = if options.translate {
+            Some("en".to_string())
+        } else {
+            detected_language.or_else(|| options.language.as_deref().map(normalize_language))
+        };
+
+        Ok(Transcription {
+            cues: VttParser::parse_content(&vtt_content)?,
+            language,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend(threads: u32) -> WhisperCppBackend {
+        WhisperCppBackend { model_path: "models/ggml-base.en.bin".to_string(), threads }
+    }
+
+    #[test]
+    fn builds_args_with_auto_language() {
+        let args = backend(4).args("/tmp/audio.wav", "/tmp/audio", &TranscriptionOptions::default());
+        assert_eq!(
+            args,
+            [
+                "-m", "models/ggml-base.en.bin", "-f", "/tmp/audio.wav", "-t", "4", "-l", "auto",
+                "-ovtt", "-of", "/tmp/audio", "-pp",
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_args_with_language_translation_and_prompt() {
+        let options = TranscriptionOptions {
+            language: Some("German".to_string()),
+            translate: true,
+            prompt: Some("ClipScribe, FFmpeg".to_string()),
+        };
+        let args = backend(2).args("a.wav", "a", &options);
+
+        assert_eq!(args[6..8], ["-l", "de"]);
+        assert_eq!(args[args.len() - 3..], ["-tr", "--prompt", "ClipScribe, FFmpeg"]);
+    }
+
+    #[test]
+    fn parses_progress_lines() {
+        assert_eq!(WhisperCppBackend::parse_progress("whisper_print_progress_callback: progress =  40%"), Some(40));
+        assert_eq!(WhisperCppBackend::parse_progress("whisper_print_progress_callback: progress = 100%"), Some(100));
+        assert_eq!(WhisperCppBackend::parse_progress("whisper_init_from_file: loading model"), None);
+    }
+
+    #[test]
+    fn parses_detected_language() {
+        assert_eq!(
+            WhisperCppBackend::parse_detected_language("whisper_full_with_state: auto-detected language: de (p = 0.974)"),
+            Some("de".to_string())
+        );
+        assert_eq!(WhisperCppBackend::parse_detected_language("system_info: n_threads = 4"), None);
+    }
+
+    #[test]
+    fn parses_whisper_cli_vtt_output() {
+        let content = include_str!("../../../tests/fixtures/vtt/whisper-cpp.vtt");
+        let cues = VttParser::parse_content(content).unwrap();
+
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[0].start_timestamp, "00:00:00.000".parse().unwrap());
+        assert_eq!(cues[0].end_timestamp, "00:00:04.320".parse().unwrap());
+        // whisper-cli starts each text line with a space
+        assert_eq!(cues[0].text, "And so my fellow Americans, ask not what your country can do for you,");
+        assert_eq!(cues[1].text, "ask what you can do for your country.");
+        assert_eq!(cues[2].end_timestamp, "00:00:11.000".parse().unwrap());
+    }
+}
@@ -1,4 +1,4 @@
-import React, { useState } from 'react';
+import React, { useRef, useState } from 'react';
 import { invoke } from '@tauri-apps/api/tauri';
 import { Settings, Sparkles, FolderOpen, AlertCircle, CheckCircle2, Loader2 } from 'lucide-react';
 import { useAppState } from './hooks/useAppState';
@@ -6,7 +6,88 @@ import { FileDropZone } from './components/FileDropZone';
 import { SettingsModal } from './components/SettingsModal';
 import { ClipReviewList } from './components/ClipReviewList';
 import { ProgressIndicator } from './components/ProgressIndicator';
-import type { Clip } from './types';
+import { JOB_CANCELLED } from './types';
+import type { AppSettings, Clip, ClipProgress, ExportProfile, HighlightReelOptions, MediaInfo, Reframe, TranscriptionOptions, TranscriptionResult } from './types';
+
+// Spoken languages offered for transcription (Whisper detects others on auto)
+const TRANSCRIPTION_LANGUAGES: [string, string][] = [
+  ['en', 'English'],
+  ['es', 'Spanish'],
+  ['de', 'German'],
+  ['fr', 'French'],
+  ['it', 'Italian'],
+  ['pt', 'Portuguese'],
+  ['nl', 'Dutch'],
+  ['sv', 'Swedish'],
+  ['da', 'Danish'],
+  ['no', 'Norwegian'],
+  ['fi', 'Finnish'],
+  ['pl', 'Polish'],
+  ['cs', 'Czech'],
+  ['ru', 'Russian'],
+  ['uk', 'Ukrainian'],
+  ['tr', 'Turkish'],
+  ['el', 'Greek'],
+  ['ar', 'Arabic'],
+  ['he', 'Hebrew'],
+  ['hi', 'Hindi'],
+  ['ja', 'Japanese'],
+  ['ko', 'Korean'],
+  ['zh', 'Chinese'],
+  ['id', 'Indonesian'],
+  ['vi', 'Vietnamese'],
+  ['th', 'Thai'],
+];
+
+const formatEta = (seconds: number) => {
+  const minutes = Math.floor(seconds / 60);
+  const secs = Math.round(seconds % 60);
+  return `${minutes}:${secs.toString().padStart(2, '0')}`;
+};
+
+// "Encoding clip 2 of 5 (40%) · 2.3x · about 1:05 left", or with parallel
+// workers "Encoding 3 clips at once (1 of 5 done) · ..."
+const describeClipProgress = (details: ClipProgress) => {
+  const parts = [
+    details.active > 1
+      ? `Encoding ${details.active} clips at once (${details.current} of ${details.total} done)`
+      : `Encoding clip ${Math.min(details.clip_index + 1, details.total)} of ${details.total} (${Math.round(details.clip_percent)}%)`,
+  ];
+  if (details.speed) {
+    parts.push(`${details.speed.toFixed(1)}x`);
+  }
+  if (details.eta_seconds !== null) {
+    parts.push(`about ${formatEta(details.eta_seconds)} left`);
+  }
+  return parts.join(' · ');
+};
+
+const languageName = (code: string) =>
+  TRANSCRIPTION_LANGUAGES.find(([known]) => known === code)?.[1] ?? code;
+
+// "00:42:10 · 1920×1080 · 29.97 fps · h264 · 2 audio tracks (aac en, aac de)"
+const describeMedia = (info: MediaInfo) => {
+  const parts = [info.duration.split('.')[0]];
+  if (info.video) {
+    parts.push(`${info.video.width}×${info.video.height}`);
+    if (info.video.frame_rate) {
+      parts.push(`${Number(info.video.frame_rate.toFixed(2))} fps`);
+    }
+    parts.push(info.video.codec);
+  } else {
+    parts.push('no video stream');
+  }
+  if (info.audio_tracks.length === 0) {
+    parts.push('no audio');
+  } else {
+    const tracks = info.audio_tracks
+      .map(track => [track.codec, track.channel_layout, track.language].filter(Boolean).join(' '))
+      .join(', ');
+    const count = info.audio_tracks.length;
+    parts.push(`${count} audio track${count === 1 ? '' : 's'} (${tracks})`);
+  }
+  return parts.join(' · ');
+};
 
 function App() {
   const {
@@ -17,8 +98,11 @@ function App() {
     setTranscriptPath,
     context,
     setContext,
+    speakers,
     analyzeClips,
     generateClips,
+    generateHighlightReel,
+    cancelClipGeneration,
     reset,
   } = useAppState();
 
@@ -26,22 +110,51 @@ function App() {
   const [clips, setClips] = useState<Clip[]>([]);
   const [isGeneratingTranscript, setIsGeneratingTranscript] = useState(false);
   const [transcriptProgress, setTranscriptProgress] = useState('');
+  const [analysisProgress, setAnalysisProgress] = useState('');
+  const [transcriptionOptions, setTranscriptionOptions] = useState<TranscriptionOptions>({
+    language: null,
+    translate: false,
+  });
+  const [transcriptInfo, setTranscriptInfo] = useState<TranscriptionResult | null>(null);
+  const [mediaInfo, setMediaInfo] = useState<MediaInfo | null>(null);
+  const [mediaError, setMediaError] = useState<string | null>(null);
+  const transcriptJobId = useRef<string | null>(null);
+  const [exportProfiles, setExportProfiles] = useState<ExportProfile[]>([]);
+  const [exportProfileId, setExportProfileId] = useState('');
+  const [burnCaptions, setBurnCaptions] = useState<boolean | null>(null);
+  const [reelOptions, setReelOptions] = useState<Omit<HighlightReelOptions, 'profile_id'>>({
+    transition: 'crossfade',
+    transition_seconds: 0.75,
+    title_cards: false,
+    title_card_seconds: 2,
+    audio_crossfade: true,
+  });
 
   // Update local clips when state changes
   React.useEffect(() => {
     if (state.status === 'review') {
       setClips(state.clips);
     }
+    if (state.status !== 'analyzing') {
+      setAnalysisProgress('');
+    }
   }, [state]);
 
-  // Listen for transcription progress
+  // Listen for transcription and analysis progress
   React.useEffect(() => {
     const setupListener = async () => {
       const { listen } = await import('@tauri-apps/api/event');
-      const unlisten = await listen<string>('transcription-progress', (event) => {
+      const unlistenTranscription = await listen<string>('transcription-progress', (event) => {
         setTranscriptProgress(event.payload);
       });
-      return unlisten;
+      // Analysis only reports retries (rate limits, server errors)
+      const unlistenAnalysis = await listen<string>('analysis-progress', (event) => {
+        setAnalysisProgress(event.payload);
+      });
+      return () => {
+        unlistenTranscription();
+        unlistenAnalysis();
+      };
     };
 
     const unlistenPromise = setupListener();
@@ -50,6 +163,21 @@ function App() {
     };
   }, []);
 
+  // Inspect the video as soon as it's picked so problems show up before analysis
+  React.useEffect(() => {
+    setMediaInfo(null);
+    setMediaError(null);
+    if (!videoPath) return;
+
+    let stale = false;
+    invoke<MediaInfo>('probe_video', { videoPath })
+      .then(info => { if (!stale) setMediaInfo(info); })
+      .catch(error => { if (!stale) setMediaError(String(error)); });
+    return () => {
+      stale = true;
+    };
+  }, [videoPath]);
+
   const handleToggleClip = (clipId: string) => {
     setClips(prev => prev.map(clip => 
       clip.id === clipId 
@@ -58,9 +186,52 @@ function App() {
     ));
   };
 
+  // Export profiles come from settings, which may have changed since last time
+  React.useEffect(() => {
+    if (state.status !== 'review') return;
+    invoke<AppSettings>('get_settings')
+      .then(settings => {
+        setExportProfiles(settings.export.profiles);
+        setExportProfileId(prev => prev || settings.export.default_profile);
+        setBurnCaptions(prev => prev ?? settings.captions.enabled);
+      })
+      .catch(error => console.error('Failed to load export settings:', error));
+  }, [state.status]);
+
+  const handleClipProfileChange = (clipId: string, profileId: string | undefined) => {
+    setClips(prev => prev.map(clip =>
+      clip.id === clipId ? { ...clip, profileId } : clip
+    ));
+  };
+
+  const handleClipReframeChange = (clipId: string, reframe: Reframe | undefined) => {
+    setClips(prev => prev.map(clip =>
+      clip.id === clipId ? { ...clip, reframe } : clip
+    ));
+  };
+
+  const handleReframeAll = (reframe: Reframe | undefined) => {
+    setClips(prev => prev.map(clip => ({ ...clip, reframe })));
+  };
+
   const handleGenerate = () => {
-    const selectedClips = clips.filter(c => c.isSelected);
-    generateClips(selectedClips);
+    generateClips(clips, exportProfileId || null, burnCaptions);
+  };
+
+  // Clip order only matters for the highlight reel
+  const handleMoveClip = (clipId: string, offset: number) => {
+    setClips(prev => {
+      const from = prev.findIndex(clip => clip.id === clipId);
+      const to = from + offset;
+      if (from < 0 || to < 0 || to >= prev.length) return prev;
+      const next = [...prev];
+      [next[from], next[to]] = [next[to], next[from]];
+      return next;
+    });
+  };
+
+  const handleGenerateReel = () => {
+    generateHighlightReel(clips, { ...reelOptions, profile_id: exportProfileId || null });
   };
 
   const handleOpenFolder = async () => {
@@ -76,23 +247,39 @@ function App() {
   const handleGenerateTranscript = async () => {
     if (!videoPath) return;
 
+    const jobId = crypto.randomUUID();
+    transcriptJobId.current = jobId;
     setIsGeneratingTranscript(true);
     setTranscriptProgress('Starting transcription...');
 
     try {
-      const vttPath = await invoke<string>('generate_transcript_from_video', {
+      const result = await invoke<TranscriptionResult>('generate_transcript_from_video', {
         videoPath,
+        options: transcriptionOptions,
+        jobId,
       });
-      setTranscriptPath(vttPath);
+      setTranscriptPath(result.vtt_path);
+      setTranscriptInfo(result);
       setTranscriptProgress('Transcript generated!');
       setTimeout(() => {
         setIsGeneratingTranscript(false);
         setTranscriptProgress('');
       }, 1000);
     } catch (error) {
-      alert(`Failed to generate transcript: ${error}`);
+      if (error !== JOB_CANCELLED) {
+        alert(`Failed to generate transcript: ${error}`);
+      }
       setIsGeneratingTranscript(false);
       setTranscriptProgress('');
+    } finally {
+      transcriptJobId.current = null;
+    }
+  };
+
+  const handleCancelTranscript = async () => {
+    if (transcriptJobId.current) {
+      setTranscriptProgress('Cancelling...');
+      await invoke('cancel_job', { jobId: transcriptJobId.current });
     }
   };
 
@@ -140,13 +327,29 @@ function App() {
               />
               
               <FileDropZone
-                accept={['vtt', 'txt']}
+                accept={['vtt', 'srt', 'txt']}
                 label="Transcript File"
-                onFileSelected={setTranscriptPath}
+                onFileSelected={(path) => {
+                  setTranscriptPath(path);
+                  setTranscriptInfo(null);
+                }}
                 currentPath={transcriptPath}
               />
             </div>
 
+            {videoPath && (mediaInfo || mediaError) && (
+              <p className={`-mt-3 text-xs ${mediaError ? 'text-red-600 dark:text-red-400' : 'text-gray-500 dark:text-gray-400'}`}>
+                {mediaInfo ? `Video: ${describeMedia(mediaInfo)}` : `Could not read video details: ${mediaError}`}
+              </p>
+            )}
+
+            {transcriptPath && transcriptInfo?.language && (
+              <p className="-mt-3 text-xs text-gray-500 dark:text-gray-400">
+                Transcript language: {languageName(transcriptInfo.language)}
+                {transcriptInfo.translated ? ' (translated)' : ''}
+              </p>
+            )}
+
             <div>
               <label className="block text-sm font-medium mb-2">
                 Context (Optional)
@@ -158,6 +361,11 @@ function App() {
                 className="w-full px-4 py-3 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 focus:ring-2 focus:ring-blue-500 focus:border-transparent resize-none"
                 rows={3}
               />
+              {speakers.length > 0 && (
+                <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                  Speakers in transcript: {speakers.join(', ')} — you can refer to them in your context
+                </p>
+              )}
             </div>
 
             {!transcriptPath && videoPath && (
@@ -165,6 +373,28 @@ function App() {
                 <p className="text-sm text-blue-700 dark:text-blue-300 mb-3">
                   💡 No transcript? We can generate one automatically using AI!
                 </p>
+                <div className="flex items-center gap-3 mb-3">
+                  <select
+                    value={transcriptionOptions.language ?? ''}
+                    onChange={(e) => setTranscriptionOptions(prev => ({ ...prev, language: e.target.value || null }))}
+                    disabled={isGeneratingTranscript}
+                    className="flex-1 px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  >
+                    <option value="">Auto-detect language</option>
+                    {TRANSCRIPTION_LANGUAGES.map(([code, name]) => (
+                      <option key={code} value={code}>{name}</option>
+                    ))}
+                  </select>
+                  <label className="flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300">
+                    <input
+                      type="checkbox"
+                      checked={transcriptionOptions.translate}
+                      onChange={(e) => setTranscriptionOptions(prev => ({ ...prev, translate: e.target.checked }))}
+                      disabled={isGeneratingTranscript}
+                    />
+                    Translate to English
+                  </label>
+                </div>
                 <button
                   onClick={handleGenerateTranscript}
                   disabled={!canGenerateTranscript || isGeneratingTranscript}
@@ -182,6 +412,14 @@ function App() {
                     </>
                   )}
                 </button>
+                {isGeneratingTranscript && (
+                  <button
+                    onClick={handleCancelTranscript}
+                    className="w-full mt-2 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors"
+                  >
+                    Cancel
+                  </button>
+                )}
                 <p className="text-xs text-gray-500 dark:text-gray-400 mt-2 text-center">
                   Cost: ~$0.006 per minute of audio
                 </p>
@@ -200,21 +438,35 @@ function App() {
         )}
 
         {state.status === 'analyzing' && (
-          <ProgressIndicator message="Analyzing transcript with AI..." />
+          <ProgressIndicator message={analysisProgress || 'Analyzing transcript with AI...'} />
         )}
 
         {state.status === 'review' && (
           <ClipReviewList
             clips={clips}
+            profiles={exportProfiles}
+            profileId={exportProfileId}
             onToggleClip={handleToggleClip}
+            onProfileChange={setExportProfileId}
+            onClipProfileChange={handleClipProfileChange}
+            sourceSize={mediaInfo?.video ? { width: mediaInfo.video.width, height: mediaInfo.video.height } : null}
+            onClipReframeChange={handleClipReframeChange}
+            onReframeAll={handleReframeAll}
+            burnCaptions={burnCaptions ?? false}
+            onBurnCaptionsChange={setBurnCaptions}
             onGenerate={handleGenerate}
+            onMoveClip={handleMoveClip}
+            reelOptions={reelOptions}
+            onReelOptionsChange={setReelOptions}
+            onGenerateReel={handleGenerateReel}
           />
         )}
 
         {state.status === 'processing' && (
           <ProgressIndicator 
             progress={state.progress}
-            message="Generating video clips..."
+            message={state.details ? describeClipProgress(state.details) : 'Generating video clips...'}
+            onCancel={cancelClipGeneration}
           />
         )}
 
@@ -226,9 +478,30 @@ function App() {
                 Success!
               </h2>
               <p className="text-gray-600 dark:text-gray-400 text-lg">
-                Generated {state.clipCount} clip{state.clipCount !== 1 ? 's' : ''}
+                {state.reelPath
+                  ? `Combined ${state.clipCount} clip${state.clipCount !== 1 ? 's' : ''} into a highlight reel`
+                  : `Generated ${state.clipCount} clip${state.clipCount !== 1 ? 's' : ''}`}
               </p>
+              {state.reelPath && (
+                <p className="text-sm text-gray-500 dark:text-gray-500 font-mono mt-1">{state.reelPath}</p>
+              )}
             </div>
+
+            {state.failedClips.length > 0 && (
+              <div className="max-w-xl mx-auto p-4 text-left bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg">
+                <p className="flex items-center gap-2 font-medium text-red-700 dark:text-red-400 mb-2">
+                  <AlertCircle className="w-5 h-5" />
+                  {state.failedClips.length} clip{state.failedClips.length !== 1 ? 's' : ''} failed
+                </p>
+                <ul className="space-y-1 text-sm text-red-700 dark:text-red-400">
+                  {state.failedClips.map(clip => (
+                    <li key={clip.id}>
+                      <span className="font-medium">{clip.title}</span>: {clip.error}
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
             
             <div className="flex justify-center gap-4">
               <button
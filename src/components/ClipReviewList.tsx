@@ -1,15 +1,164 @@
 import React from 'react';
-import { Clip } from '../types';
-import { Clock, CheckCircle2, Circle } from 'lucide-react';
+import { AspectRatio, Clip, ExportProfile, HighlightReelOptions, Reframe, ReelTransition, ReframeMode } from '../types';
+import { ArrowDown, ArrowUp, Clock, CheckCircle2, Circle, Crop, Film, Users } from 'lucide-react';
+
+interface SourceSize {
+  width: number;
+  height: number;
+}
+
+const REFRAME_MODES: [ReframeMode, string][] = [
+  ['center', 'Center crop'],
+  ['offset', 'Crop at position'],
+  ['crop', 'Custom crop'],
+  ['blurred_background', 'Blurred background'],
+];
+
+// Largest centered region of the source with the target shape
+const defaultCrop = (aspect: AspectRatio, source: SourceSize | null) => {
+  const [ratioW, ratioH] = aspect === '9:16' ? [9, 16] : [1, 1];
+  const { width, height } = source ?? { width: 1920, height: 1080 };
+  const cropWidth = Math.min(width, Math.round(height * ratioW / ratioH));
+  const cropHeight = Math.min(height, Math.round(width * ratioH / ratioW));
+  return {
+    x: Math.round((width - cropWidth) / 2),
+    y: Math.round((height - cropHeight) / 2),
+    width: cropWidth,
+    height: cropHeight,
+  };
+};
+
+const reframeWithMode = (aspect: AspectRatio, mode: ReframeMode, source: SourceSize | null): Reframe => {
+  switch (mode) {
+    case 'offset':
+      return { aspect, mode, position: 0.5 };
+    case 'crop':
+      return { aspect, mode, ...defaultCrop(aspect, source) };
+    default:
+      return { aspect, mode };
+  }
+};
+
+const REEL_TRANSITIONS: [ReelTransition, string][] = [
+  ['cut', 'Cut'],
+  ['crossfade', 'Crossfade'],
+  ['dip_to_black', 'Dip to black'],
+];
+
+const selectClass = 'px-2 py-1 text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700';
+const numberClass = 'w-16 px-2 py-1 text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700';
+
+interface ReframeControlsProps {
+  reframe: Reframe | undefined;
+  sourceSize: SourceSize | null;
+  onChange: (reframe: Reframe | undefined) => void;
+}
+
+// Aspect ratio and fit for one clip
+function ReframeControls({ reframe, sourceSize, onChange }: ReframeControlsProps) {
+  return (
+    <div className="flex flex-wrap items-center gap-2" onClick={(e) => e.stopPropagation()}>
+      <Crop className="w-4 h-4 text-gray-500" />
+      <select
+        value={reframe?.aspect ?? ''}
+        onChange={(e) => {
+          const aspect = e.target.value as AspectRatio | '';
+          onChange(aspect ? reframeWithMode(aspect, reframe?.mode ?? 'center', sourceSize) : undefined);
+        }}
+        className={selectClass}
+      >
+        <option value="">Original frame</option>
+        <option value="9:16">9:16 vertical</option>
+        <option value="1:1">1:1 square</option>
+      </select>
+
+      {reframe && (
+        <select
+          value={reframe.mode}
+          onChange={(e) => onChange(reframeWithMode(reframe.aspect, e.target.value as ReframeMode, sourceSize))}
+          className={selectClass}
+        >
+          {REFRAME_MODES.map(([mode, label]) => (
+            <option key={mode} value={mode}>{label}</option>
+          ))}
+        </select>
+      )}
+
+      {reframe?.mode === 'offset' && (
+        <label className="flex items-center gap-2 text-xs text-gray-600 dark:text-gray-400">
+          Left
+          <input
+            type="range"
+            min={0}
+            max={100}
+            value={Math.round(reframe.position * 100)}
+            onChange={(e) => onChange({ ...reframe, position: Number(e.target.value) / 100 })}
+          />
+          Right
+        </label>
+      )}
+
+      {reframe?.mode === 'crop' && (
+        <div className="flex items-center gap-1 text-xs text-gray-600 dark:text-gray-400">
+          {(['x', 'y', 'width', 'height'] as const).map(field => (
+            <label key={field} className="flex items-center gap-1">
+              {field === 'width' ? 'W' : field === 'height' ? 'H' : field.toUpperCase()}
+              <input
+                type="number"
+                min={field === 'width' || field === 'height' ? 2 : 0}
+                value={reframe[field]}
+                onChange={(e) => onChange({ ...reframe, [field]: Math.max(0, Math.round(Number(e.target.value))) })}
+                className={numberClass}
+              />
+            </label>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
 
 interface ClipReviewListProps {
   clips: Clip[];
+  profiles: ExportProfile[];
+  profileId: string;                 // Profile for clips that don't set their own
   onToggleClip: (clipId: string) => void;
+  onProfileChange: (profileId: string) => void;
+  onClipProfileChange: (clipId: string, profileId: string | undefined) => void;
+  sourceSize: SourceSize | null;     // Video resolution, for default crop rectangles
+  onClipReframeChange: (clipId: string, reframe: Reframe | undefined) => void;
+  onReframeAll: (reframe: Reframe | undefined) => void;
+  burnCaptions: boolean;
+  onBurnCaptionsChange: (burnCaptions: boolean) => void;
   onGenerate: () => void;
+  onMoveClip: (clipId: string, offset: number) => void;
+  reelOptions: Omit<HighlightReelOptions, 'profile_id'>;
+  onReelOptionsChange: (options: Omit<HighlightReelOptions, 'profile_id'>) => void;
+  onGenerateReel: () => void;
 }
 
-export function ClipReviewList({ clips, onToggleClip, onGenerate }: ClipReviewListProps) {
+export function ClipReviewList({
+  clips,
+  profiles,
+  profileId,
+  onToggleClip,
+  onProfileChange,
+  onClipProfileChange,
+  sourceSize,
+  onClipReframeChange,
+  onReframeAll,
+  burnCaptions,
+  onBurnCaptionsChange,
+  onGenerate,
+  onMoveClip,
+  reelOptions,
+  onReelOptionsChange,
+  onGenerateReel,
+}: ClipReviewListProps) {
   const selectedCount = clips.filter(c => c.isSelected).length;
+  const jobProfile = profiles.find(p => p.id === profileId);
+  const profileFor = (clip: Clip) => profiles.find(p => p.id === (clip.profileId ?? profileId));
+  const extensionFor = (clip: Clip) => profileFor(clip)?.extension ?? 'mp4';
 
   return (
     <div className="space-y-6">
@@ -17,17 +166,113 @@ export function ClipReviewList({ clips, onToggleClip, onGenerate }: ClipReviewLi
         <h2 className="text-2xl font-bold">
           Review Clips ({selectedCount} selected)
         </h2>
+        <div className="flex items-center gap-3">
+          <label className="flex items-center gap-2 text-sm" title="Burn the transcript into the clips as captions">
+            <input
+              type="checkbox"
+              checked={burnCaptions}
+              onChange={(e) => onBurnCaptionsChange(e.target.checked)}
+            />
+            Captions
+          </label>
+          <select
+            value=""
+            onChange={(e) => {
+              const value = e.target.value;
+              if (value === 'original') {
+                onReframeAll(undefined);
+              } else if (value) {
+                onReframeAll(reframeWithMode(value as AspectRatio, 'center', sourceSize));
+              }
+            }}
+            className="px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+            title="Reframe every clip"
+          >
+            <option value="">Frame all clips...</option>
+            <option value="original">Original frame</option>
+            <option value="9:16">9:16 vertical (center crop)</option>
+            <option value="1:1">1:1 square (center crop)</option>
+          </select>
+          {profiles.length > 0 && (
+            <select
+              value={profileId}
+              onChange={(e) => onProfileChange(e.target.value)}
+              className="px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+              title="Export profile"
+            >
+              {profiles.map(profile => (
+                <option key={profile.id} value={profile.id}>{profile.name}</option>
+              ))}
+            </select>
+          )}
+            <button
+            onClick={onGenerate}
+            disabled={selectedCount === 0}
+            className="px-6 py-3 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors font-medium"
+          >
+            Generate {selectedCount} Clip{selectedCount !== 1 ? 's' : ''}
+          </button>
+        </div>
+      </div>
+
+      {/* Selected clips, top to bottom, joined into one video */}
+      <div className="flex flex-wrap items-center gap-3 p-3 text-sm border border-gray-200 dark:border-gray-700 rounded-lg">
+        <Film className="w-4 h-4 text-gray-500" />
+        <span className="font-medium">Highlight reel</span>
+        <select
+          value={reelOptions.transition}
+          onChange={(e) => onReelOptionsChange({ ...reelOptions, transition: e.target.value as ReelTransition })}
+          className={selectClass}
+          title="Transition between clips"
+        >
+          {REEL_TRANSITIONS.map(([transition, label]) => (
+            <option key={transition} value={transition}>{label}</option>
+          ))}
+        </select>
+        {reelOptions.transition !== 'cut' && (
+          <label className="flex items-center gap-1 text-xs text-gray-600 dark:text-gray-400">
+            <input
+              type="number"
+              min={0.1}
+              max={3}
+              step={0.25}
+              value={reelOptions.transition_seconds}
+              onChange={(e) => onReelOptionsChange({ ...reelOptions, transition_seconds: Number(e.target.value) })}
+              className={numberClass}
+            />
+            s
+          </label>
+        )}
+        <label className="flex items-center gap-2 text-xs">
+          <input
+            type="checkbox"
+            checked={reelOptions.title_cards}
+            onChange={(e) => onReelOptionsChange({ ...reelOptions, title_cards: e.target.checked })}
+          />
+          Title cards
+        </label>
+        {reelOptions.transition !== 'cut' && (
+          <label className="flex items-center gap-2 text-xs">
+            <input
+              type="checkbox"
+              checked={reelOptions.audio_crossfade}
+              onChange={(e) => onReelOptionsChange({ ...reelOptions, audio_crossfade: e.target.checked })}
+            />
+            Crossfade audio
+          </label>
+        )}
         <button
-          onClick={onGenerate}
-          disabled={selectedCount === 0}
-          className="px-6 py-3 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed transition-colors font-medium"
+          onClick={onGenerateReel}
+          disabled={selectedCount === 0 || jobProfile?.video === null}
+          className="ml-auto px-4 py-2 bg-gray-800 text-white rounded-lg hover:bg-gray-900 disabled:opacity-50 disabled:cursor-not-allowed transition-colors font-medium"
+          title={jobProfile?.video === null ? 'Pick a video export profile for the highlight reel' : undefined}
         >
-          Generate {selectedCount} Clip{selectedCount !== 1 ? 's' : ''}
+          Create Highlight Reel
         </button>
       </div>
 
       <div className="space-y-3">
-        {clips.map((clip) => (
+        {clips.map((clip, index) => (
           <div
             key={clip.id}
             onClick={() => onToggleClip(clip.id)}
@@ -47,9 +292,29 @@ export function ClipReviewList({ clips, onToggleClip, onGenerate }: ClipReviewLi
               )}
               
               <div className="flex-1 min-w-0">
-                <h3 className="font-semibold text-lg mb-2">
-                  {clip.title}
-                </h3>
+                <div className="flex items-start justify-between gap-2 mb-2">
+                  <h3 className="font-semibold text-lg">
+                    {clip.title}
+                  </h3>
+                  <div className="flex gap-1 flex-shrink-0" onClick={(e) => e.stopPropagation()}>
+                    <button
+                      onClick={() => onMoveClip(clip.id, -1)}
+                      disabled={index === 0}
+                      className="p-1 rounded hover:bg-gray-200 dark:hover:bg-gray-700 disabled:opacity-30"
+                      title="Move up"
+                    >
+                      <ArrowUp className="w-4 h-4" />
+                    </button>
+                    <button
+                      onClick={() => onMoveClip(clip.id, 1)}
+                      disabled={index === clips.length - 1}
+                      className="p-1 rounded hover:bg-gray-200 dark:hover:bg-gray-700 disabled:opacity-30"
+                      title="Move down"
+                    >
+                      <ArrowDown className="w-4 h-4" />
+                    </button>
+                  </div>
+                </div>
                 
                 <div className="flex items-center gap-2 text-sm text-gray-600 dark:text-gray-400">
                   <Clock className="w-4 h-4" />
@@ -58,9 +323,42 @@ export function ClipReviewList({ clips, onToggleClip, onGenerate }: ClipReviewLi
                   </span>
                 </div>
 
-                {clip.sanitizedFilename && (
-                  <div className="mt-2 text-xs text-gray-500 dark:text-gray-500 font-mono">
-                    {clip.sanitizedFilename}.mp4
+                {clip.speakers && clip.speakers.length > 0 && (
+                  <div className="flex items-center gap-2 mt-1 text-sm text-gray-600 dark:text-gray-400">
+                    <Users className="w-4 h-4" />
+                    <span>{clip.speakers.join(', ')}</span>
+                  </div>
+                )}
+
+                <div className="flex items-center gap-3 mt-2">
+                  {clip.sanitizedFilename && (
+                    <div className="text-xs text-gray-500 dark:text-gray-500 font-mono">
+                      {clip.sanitizedFilename}.{extensionFor(clip)}
+                    </div>
+                  )}
+                  {profiles.length > 0 && (
+                    <select
+                      value={clip.profileId ?? ''}
+                      onClick={(e) => e.stopPropagation()}
+                      onChange={(e) => onClipProfileChange(clip.id, e.target.value || undefined)}
+                      className="px-2 py-1 text-xs border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700"
+                    >
+                      <option value="">Same as job</option>
+                      {profiles.map(profile => (
+                        <option key={profile.id} value={profile.id}>{profile.name}</option>
+                      ))}
+                    </select>
+                  )}
+                </div>
+
+                {/* Audio-only profiles have no frame to reshape */}
+                {profileFor(clip)?.video !== null && (
+                  <div className="mt-2">
+                    <ReframeControls
+                      reframe={clip.reframe}
+                      sourceSize={sourceSize}
+                      onChange={(reframe) => onClipReframeChange(clip.id, reframe)}
+                    />
                   </div>
                 )}
               </div>
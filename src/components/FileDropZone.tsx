@@ -3,7 +3,7 @@ import { open } from '@tauri-apps/api/dialog';
 import { Upload } from 'lucide-react';
 
 interface FileDropZoneProps {
-  accept: string[];  // e.g., ["mp4", "mov", "mkv"] or ["vtt", "txt"]
+  accept: string[];  // e.g., ["mp4", "mov", "mkv"] or ["vtt", "srt", "txt"]
   label: string;
   onFileSelected: (path: string) => void;
   currentPath: string | null;
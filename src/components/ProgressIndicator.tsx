@@ -4,9 +4,10 @@ import { Loader2 } from 'lucide-react';
 interface ProgressIndicatorProps {
   progress?: number;
   message?: string;
+  onCancel?: () => void;
 }
 
-export function ProgressIndicator({ progress, message }: ProgressIndicatorProps) {
+export function ProgressIndicator({ progress, message, onCancel }: ProgressIndicatorProps) {
   return (
     <div className="flex flex-col items-center justify-center py-12 space-y-6">
       <Loader2 className="w-16 h-16 text-blue-600 animate-spin" />
@@ -31,6 +32,15 @@ export function ProgressIndicator({ progress, message }: ProgressIndicatorProps)
           </div>
         </div>
       )}
+
+      {onCancel && (
+        <button
+          onClick={onCancel}
+          className="px-4 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg hover:bg-gray-100 dark:hover:bg-gray-700 transition-colors"
+        >
+          Cancel
+        </button>
+      )}
     </div>
   );
 }
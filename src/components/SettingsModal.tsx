@@ -1,6 +1,8 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/tauri';
+import { open } from '@tauri-apps/api/dialog';
 import { X, Key, Loader2 } from 'lucide-react';
+import type { AppSettings, CaptionPosition, ExportMode, LlmProviderKind, SubtitleFormat, TranscriptionBackendKind } from '../types';
 
 interface SettingsModalProps {
   isOpen: boolean;
@@ -12,13 +14,80 @@ export function SettingsModal({ isOpen, onClose }: SettingsModalProps) {
   const [isValidating, setIsValidating] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [success, setSuccess] = useState(false);
+  const [settings, setSettings] = useState<AppSettings | null>(null);
 
   useEffect(() => {
     if (isOpen) {
       loadApiKey();
+      loadSettings();
     }
   }, [isOpen]);
 
+  const loadSettings = async () => {
+    try {
+      setSettings(await invoke<AppSettings>('get_settings'));
+    } catch (error) {
+      console.error('Failed to load settings:', error);
+    }
+  };
+
+  const updateAnalysis = (changes: Partial<AppSettings['analysis']>) => {
+    setSettings(prev => prev && { ...prev, analysis: { ...prev.analysis, ...changes } });
+    setError(null);
+    setSuccess(false);
+  };
+
+  const updateNetwork = (changes: Partial<AppSettings['network']>) => {
+    setSettings(prev => prev && { ...prev, network: { ...prev.network, ...changes } });
+    setError(null);
+    setSuccess(false);
+  };
+
+  const updateTranscription = (changes: Partial<AppSettings['transcription']>) => {
+    setSettings(prev => prev && { ...prev, transcription: { ...prev.transcription, ...changes } });
+    setError(null);
+    setSuccess(false);
+  };
+
+  const updateExport = (changes: Partial<AppSettings['export']>) => {
+    setSettings(prev => prev && { ...prev, export: { ...prev.export, ...changes } });
+    setError(null);
+    setSuccess(false);
+  };
+
+  const toggleSubtitleFile = (format: SubtitleFormat, enabled: boolean) => {
+    setSettings(prev => prev && {
+      ...prev,
+      export: {
+        ...prev.export,
+        subtitle_files: enabled
+          ? [...prev.export.subtitle_files.filter(f => f !== format), format]
+          : prev.export.subtitle_files.filter(f => f !== format),
+      },
+    });
+    setError(null);
+    setSuccess(false);
+  };
+
+  const updateCaptions = (changes: Partial<AppSettings['captions']>) => {
+    setSettings(prev => prev && { ...prev, captions: { ...prev.captions, ...changes } });
+    setError(null);
+    setSuccess(false);
+  };
+
+  const handleBrowseModel = async () => {
+    const selected = await open({
+      multiple: false,
+      filters: [{ name: 'GGML Model', extensions: ['bin'] }],
+    });
+    if (typeof selected === 'string') {
+      updateTranscription({ whisper_cpp_model_path: selected });
+    }
+  };
+
+  const isLocalProvider = settings?.analysis.provider === 'openai_compatible';
+  const isLocalTranscription = settings?.transcription.backend === 'whisper_cpp';
+
   const loadApiKey = async () => {
     try {
       const key = await invoke<string | null>('get_api_key');
@@ -31,7 +100,10 @@ export function SettingsModal({ isOpen, onClose }: SettingsModalProps) {
   };
 
   const handleSave = async () => {
-    if (!apiKey || apiKey.startsWith('***')) {
+    const hasNewKey = apiKey && !apiKey.startsWith('***');
+
+    // OpenAI providers need a key; local servers don't
+    if (!apiKey && !isLocalProvider) {
       setError('Please enter a valid API key');
       return;
     }
@@ -41,13 +113,19 @@ export function SettingsModal({ isOpen, onClose }: SettingsModalProps) {
     setSuccess(false);
 
     try {
-      const isValid = await invoke<boolean>('validate_api_key', { apiKey });
-      if (!isValid) {
-        setError('Invalid API key. Please check and try again.');
-        return;
+      if (hasNewKey) {
+        const isValid = await invoke<boolean>('validate_api_key', { apiKey });
+        if (!isValid) {
+          setError('Invalid API key. Please check and try again.');
+          return;
+        }
+
+        await invoke('save_api_key', { apiKey });
+      }
+
+      if (settings) {
+        await invoke('save_settings', { settings });
       }
-      
-      await invoke('save_api_key', { apiKey });
       setSuccess(true);
       setTimeout(() => {
         onClose();
@@ -77,7 +155,7 @@ export function SettingsModal({ isOpen, onClose }: SettingsModalProps) {
           </button>
         </div>
 
-        <div className="p-6 space-y-4">
+        <div className="p-6 space-y-4 max-h-[60vh] overflow-y-auto">
           <div>
             <label className="block text-sm font-medium mb-2">
               OpenAI API Key
@@ -106,6 +184,524 @@ export function SettingsModal({ isOpen, onClose }: SettingsModalProps) {
             </p>
           </div>
 
+          {settings && (
+            <div className="space-y-3 pt-4 border-t border-gray-200 dark:border-gray-700">
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Analysis Provider
+                </label>
+                <select
+                  value={settings.analysis.provider}
+                  onChange={(e) => updateAnalysis({ provider: e.target.value as LlmProviderKind })}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                >
+                  <option value="responses">OpenAI Responses API</option>
+                  <option value="chat_completions">OpenAI Chat Completions API</option>
+                  <option value="openai_compatible">Local / OpenAI-compatible server</option>
+                </select>
+                {isLocalProvider && (
+                  <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                    Transcripts are sent only to this server (Ollama, llama.cpp server, vLLM...).
+                  </p>
+                )}
+              </div>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Base URL
+                </label>
+                <input
+                  type="text"
+                  value={settings.analysis.base_url ?? ''}
+                  onChange={(e) => updateAnalysis({ base_url: e.target.value || null })}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  placeholder={isLocalProvider ? 'http://localhost:11434/v1' : 'https://api.openai.com/v1'}
+                />
+              </div>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Model
+                </label>
+                <input
+                  type="text"
+                  value={settings.analysis.model}
+                  onChange={(e) => updateAnalysis({ model: e.target.value })}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  placeholder={isLocalProvider ? 'llama3.1:8b' : 'gpt-5-mini'}
+                />
+              </div>
+
+              {isLocalProvider ? (
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Server API Key (Optional)
+                  </label>
+                  <input
+                    type="password"
+                    value={settings.analysis.api_key ?? ''}
+                    onChange={(e) => updateAnalysis({ api_key: e.target.value || null })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                  <label className="flex items-center gap-2 mt-3 text-sm">
+                    <input
+                      type="checkbox"
+                      checked={settings.analysis.structured_output}
+                      onChange={(e) => updateAnalysis({ structured_output: e.target.checked })}
+                    />
+                    Request JSON schema output (turn off if the server rejects it)
+                  </label>
+                </div>
+              ) : (
+                <div className="grid grid-cols-2 gap-3">
+                  <div>
+                    <label className="block text-sm font-medium mb-2">
+                      Reasoning Effort
+                    </label>
+                    <select
+                      value={settings.analysis.reasoning_effort}
+                      onChange={(e) => updateAnalysis({ reasoning_effort: e.target.value })}
+                      className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                    >
+                      <option value="minimal">Minimal</option>
+                      <option value="low">Low</option>
+                      <option value="medium">Medium</option>
+                      <option value="high">High</option>
+                    </select>
+                  </div>
+                  <div>
+                    <label className="block text-sm font-medium mb-2">
+                      Verbosity
+                    </label>
+                    <select
+                      value={settings.analysis.verbosity}
+                      onChange={(e) => updateAnalysis({ verbosity: e.target.value })}
+                      className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                    >
+                      <option value="low">Low</option>
+                      <option value="medium">Medium</option>
+                      <option value="high">High</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+
+              <div className="grid grid-cols-2 gap-3">
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Analysis Window (min)
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.analysis.window_minutes}
+                    onChange={(e) => updateAnalysis({ window_minutes: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Max Clips
+                  </label>
+                  <input
+                    type="number"
+                    min={1}
+                    value={settings.analysis.max_clips}
+                    onChange={(e) => updateAnalysis({ max_clips: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+              </div>
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                Transcripts longer than the window are analyzed in overlapping sections, then the best clips across the whole video are kept.
+              </p>
+
+              <div className="grid grid-cols-2 gap-3">
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Padding Before (ms)
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.analysis.pre_roll_ms}
+                    onChange={(e) => updateAnalysis({ pre_roll_ms: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Padding After (ms)
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.analysis.post_roll_ms}
+                    onChange={(e) => updateAnalysis({ post_roll_ms: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+              </div>
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                Added around each suggested clip so it doesn't cut off the first syllable or end abruptly.
+              </p>
+
+              <div>
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={settings.analysis.snap_to_sentences}
+                    onChange={(e) => updateAnalysis({ snap_to_sentences: e.target.checked })}
+                  />
+                  Start and end clips on whole sentences
+                </label>
+                {settings.analysis.snap_to_sentences && (
+                  <div className="mt-2 flex items-center gap-2 text-sm">
+                    <span>Move edges by up to</span>
+                    <input
+                      type="number"
+                      min={1}
+                      value={settings.analysis.sentence_snap_seconds}
+                      onChange={(e) => updateAnalysis({ sentence_snap_seconds: Number(e.target.value) })}
+                      className="w-20 px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                    />
+                    <span>seconds</span>
+                  </div>
+                )}
+                <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                  Uses the transcript's punctuation, so it works best with punctuated transcripts.
+                </p>
+              </div>
+
+              <div className="grid grid-cols-2 gap-3">
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Request Timeout (s)
+                  </label>
+                  <input
+                    type="number"
+                    min={1}
+                    value={settings.network.request_timeout_seconds}
+                    onChange={(e) => updateNetwork({ request_timeout_seconds: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Max Retries
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.network.max_retries}
+                    onChange={(e) => updateNetwork({ max_retries: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+              </div>
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                Rate limits, server errors and dropped connections are retried with increasing delays.
+              </p>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Transcription
+                </label>
+                <select
+                  value={settings.transcription.backend}
+                  onChange={(e) => updateTranscription({ backend: e.target.value as TranscriptionBackendKind })}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                >
+                  <option value="openai_whisper">OpenAI Whisper</option>
+                  <option value="whisper_cpp">Local whisper.cpp (offline)</option>
+                </select>
+              </div>
+
+              {isLocalTranscription && (
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Whisper Model File
+                  </label>
+                  <div className="flex gap-2">
+                    <input
+                      type="text"
+                      value={settings.transcription.whisper_cpp_model_path ?? ''}
+                      onChange={(e) => updateTranscription({ whisper_cpp_model_path: e.target.value || null })}
+                      className="flex-1 min-w-0 px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                      placeholder="ggml-base.en.bin"
+                    />
+                    <button
+                      type="button"
+                      onClick={handleBrowseModel}
+                      className="px-3 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-lg hover:bg-gray-100 dark:hover:bg-gray-700"
+                    >
+                      Browse
+                    </button>
+                  </div>
+                  <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                    Audio never leaves this computer. Transcription runs on the CPU, so larger models are slower.
+                  </p>
+                </div>
+              )}
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Glossary
+                </label>
+                <textarea
+                  value={settings.transcription.glossary}
+                  onChange={(e) => updateTranscription({ glossary: e.target.value })}
+                  rows={4}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 font-mono text-sm resize-none"
+                  placeholder={'ClipScribe\nclip scribe, clipscribe => ClipScribe'}
+                />
+                <label className="flex items-center gap-2 mt-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={settings.transcription.apply_glossary_replacements}
+                    onChange={(e) => updateTranscription({ apply_glossary_replacements: e.target.checked })}
+                  />
+                  Fix listed misspellings after transcription
+                </label>
+                <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                  Names and terms are passed to Whisper as hints. A glossary.txt in the video's folder adds project-specific entries.
+                </p>
+              </div>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Default Export Profile
+                </label>
+                <select
+                  value={settings.export.default_profile}
+                  onChange={(e) => updateExport({ default_profile: e.target.value })}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                >
+                  {settings.export.profiles.map(profile => (
+                    <option key={profile.id} value={profile.id}>{profile.name}</option>
+                  ))}
+                </select>
+              </div>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Clip Export
+                </label>
+                <select
+                  value={settings.export.mode}
+                  onChange={(e) => updateExport({ mode: e.target.value as ExportMode })}
+                  className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                >
+                  <option value="reencode">Re-encode with the export profile (any source)</option>
+                  <option value="stream_copy">Lossless copy when possible</option>
+                  <option value="smart_render">Lossless copy, re-encode only at cuts</option>
+                </select>
+                <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                  Copying is much faster and keeps the original quality for H.264/HEVC sources. Clips that can't be copied are re-encoded.
+                </p>
+              </div>
+
+              <div className="grid grid-cols-2 gap-3">
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Clips at Once
+                  </label>
+                  <input
+                    type="number"
+                    min={1}
+                    value={settings.export.max_concurrent_clips}
+                    onChange={(e) => updateExport({ max_concurrent_clips: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Threads per Clip
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.export.threads_per_clip}
+                    onChange={(e) => updateExport({ threads_per_clip: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+              </div>
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                Encoding several clips in parallel uses more of the CPU on short clips. 0 threads lets FFmpeg decide; lower it if the computer becomes unresponsive.
+              </p>
+
+              <div className="grid grid-cols-3 gap-3">
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Fade In (ms)
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.export.fade_in_ms}
+                    onChange={(e) => updateExport({ fade_in_ms: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Fade Out (ms)
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.export.fade_out_ms}
+                    onChange={(e) => updateExport({ fade_out_ms: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div>
+                  <label className="block text-sm font-medium mb-2">
+                    Handles (s)
+                  </label>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.export.handle_seconds}
+                    onChange={(e) => updateExport({ handle_seconds: Number(e.target.value) })}
+                    className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+              </div>
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                Fades apply to both picture and sound. Handles export extra seconds before and after each clip for trimming in an editor; the real in and out points are saved in the file's comment, and fades are left off.
+              </p>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Subtitle Files
+                </label>
+                <div className="flex items-center gap-4 text-sm">
+                  {(['vtt', 'srt'] as SubtitleFormat[]).map(format => (
+                    <label key={format} className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={settings.export.subtitle_files.includes(format)}
+                        onChange={(e) => toggleSubtitleFile(format, e.target.checked)}
+                      />
+                      .{format}
+                    </label>
+                  ))}
+                </div>
+                <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                  Saves the lines spoken in each clip next to it, timed from the clip's start, for uploading captions separately.
+                </p>
+              </div>
+
+              <div>
+                <label className="block text-sm font-medium mb-2">
+                  Captions
+                </label>
+                <label className="flex items-center gap-2 text-sm">
+                  <input
+                    type="checkbox"
+                    checked={settings.captions.enabled}
+                    onChange={(e) => updateCaptions({ enabled: e.target.checked })}
+                  />
+                  Burn captions into clips by default
+                </label>
+                <div className="grid grid-cols-2 gap-3 mt-3">
+                  <input
+                    type="text"
+                    value={settings.captions.font}
+                    onChange={(e) => updateCaptions({ font: e.target.value })}
+                    placeholder="Font"
+                    className="px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                  <input
+                    type="number"
+                    min={8}
+                    value={settings.captions.font_size}
+                    onChange={(e) => updateCaptions({ font_size: Number(e.target.value) })}
+                    title="Font size"
+                    className="px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                  <select
+                    value={settings.captions.position}
+                    onChange={(e) => updateCaptions({ position: e.target.value as CaptionPosition })}
+                    className="px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  >
+                    <option value="bottom">Bottom</option>
+                    <option value="middle">Middle</option>
+                    <option value="top">Top</option>
+                  </select>
+                  <input
+                    type="number"
+                    min={0}
+                    value={settings.captions.margin}
+                    onChange={(e) => updateCaptions({ margin: Number(e.target.value) })}
+                    title="Distance from the edge"
+                    className="px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700"
+                  />
+                </div>
+                <div className="flex flex-wrap items-center gap-4 mt-3 text-sm">
+                  <label className="flex items-center gap-2">
+                    <input
+                      type="color"
+                      value={settings.captions.colour}
+                      onChange={(e) => updateCaptions({ colour: e.target.value })}
+                    />
+                    Text
+                  </label>
+                  <label className="flex items-center gap-2">
+                    <input
+                      type="color"
+                      value={settings.captions.highlight_colour}
+                      onChange={(e) => updateCaptions({ highlight_colour: e.target.value })}
+                    />
+                    Highlight
+                  </label>
+                  <label className="flex items-center gap-2">
+                    <input
+                      type="color"
+                      value={settings.captions.outline_colour}
+                      onChange={(e) => updateCaptions({ outline_colour: e.target.value })}
+                    />
+                    Outline
+                  </label>
+                  <label className="flex items-center gap-2">
+                    Width
+                    <input
+                      type="number"
+                      min={0}
+                      step={0.5}
+                      value={settings.captions.outline_width}
+                      onChange={(e) => updateCaptions({ outline_width: Number(e.target.value) })}
+                      className="w-16 px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-white dark:bg-gray-700"
+                    />
+                  </label>
+                </div>
+                <div className="flex flex-wrap items-center gap-4 mt-3 text-sm">
+                  <label className="flex items-center gap-2">
+                    <input
+                      type="checkbox"
+                      checked={settings.captions.bold}
+                      onChange={(e) => updateCaptions({ bold: e.target.checked })}
+                    />
+                    Bold
+                  </label>
+                  <label className="flex items-center gap-2">
+                    <input
+                      type="checkbox"
+                      checked={settings.captions.word_highlight}
+                      onChange={(e) => updateCaptions({ word_highlight: e.target.checked })}
+                    />
+                    Highlight each word as it's spoken
+                  </label>
+                </div>
+                <p className="mt-2 text-xs text-gray-500 dark:text-gray-400">
+                  Sizes are for a 1080p frame and scale with the clip. Word highlighting needs a transcript with word timings (generated by ClipScribe with OpenAI Whisper).
+                </p>
+              </div>
+            </div>
+          )}
+
           {error && (
             <div className="p-3 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg text-red-700 dark:text-red-400 text-sm">
               {error}
@@ -114,7 +710,7 @@ export function SettingsModal({ isOpen, onClose }: SettingsModalProps) {
 
           {success && (
             <div className="p-3 bg-green-50 dark:bg-green-900/20 border border-green-200 dark:border-green-800 rounded-lg text-green-700 dark:text-green-400 text-sm">
-              API key saved successfully!
+              Settings saved successfully!
             </div>
           )}
         </div>
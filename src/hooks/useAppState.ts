@@ -1,20 +1,26 @@
-import { useState, useCallback, useEffect } from 'react';
+import { useState, useCallback, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/tauri';
 import { listen } from '@tauri-apps/api/event';
-import type { AppState, Clip, ProcessingResult, ClipProgress } from '../types';
+import { JOB_CANCELLED } from '../types';
+import type { AppState, Clip, ProcessingResult, ClipProgress, HighlightReelOptions, HighlightReelResult } from '../types';
 
 export function useAppState() {
   const [state, setState] = useState<AppState>({ status: 'ready' });
   const [videoPath, setVideoPath] = useState<string | null>(null);
   const [transcriptPath, setTranscriptPath] = useState<string | null>(null);
   const [context, setContext] = useState<string>('');
+  const [speakers, setSpeakers] = useState<string[]>([]);
+  const clipJobId = useRef<string | null>(null);
 
   // Listen for progress events
   useEffect(() => {
     const setupListener = async () => {
       const unlisten = await listen<ClipProgress>('clip-progress', (event) => {
-        const { current, total } = event.payload;
-        setState({ status: 'processing', progress: (current / total) * 100 });
+        setState({
+          status: 'processing',
+          progress: event.payload.overall_percent,
+          details: event.payload,
+        });
       });
 
       return unlisten;
@@ -27,6 +33,18 @@ export function useAppState() {
     };
   }, []);
 
+  // Load speaker names from the transcript's voice tags
+  useEffect(() => {
+    if (!transcriptPath) {
+      setSpeakers([]);
+      return;
+    }
+
+    invoke<string[]>('get_transcript_speakers', { transcriptPath })
+      .then(setSpeakers)
+      .catch(() => setSpeakers([]));
+  }, [transcriptPath]);
+
   const analyzeClips = useCallback(async () => {
     if (!videoPath || !transcriptPath) return;
 
@@ -51,27 +69,83 @@ export function useAppState() {
     }
   }, [videoPath, transcriptPath, context]);
 
-  const generateClips = useCallback(async (selectedClips: Clip[]) => {
+  // Only clips marked isSelected are generated; the rest are kept for going back to review.
+  // profileId applies to clips without their own profile (null = settings default).
+  // burnCaptions overrides the captions setting (null = use the setting).
+  const generateClips = useCallback(async (clips: Clip[], profileId: string | null, burnCaptions: boolean | null) => {
     if (!videoPath) return;
 
+    const jobId = crypto.randomUUID();
+    clipJobId.current = jobId;
     setState({ status: 'processing', progress: 0 });
 
     try {
       const result: ProcessingResult = await invoke('generate_clips', {
         videoPath,
-        clips: selectedClips,
+        clips,
+        jobId,
+        profileId,
+        transcriptPath,
+        burnCaptions,
       });
 
       setState({
         status: 'complete',
         outputPath: result.output_directory,
         clipCount: result.clip_count,
+        failedClips: result.clips.filter(clip => clip.error !== null),
       });
     } catch (error) {
-      setState({ status: 'error', message: String(error) });
+      if (error === JOB_CANCELLED) {
+        setState({ status: 'review', clips });
+      } else {
+        setState({ status: 'error', message: String(error) });
+      }
+    } finally {
+      clipJobId.current = null;
+    }
+  }, [videoPath, transcriptPath]);
+
+  // Selected clips, in list order, rendered into one video
+  const generateHighlightReel = useCallback(async (clips: Clip[], options: HighlightReelOptions) => {
+    if (!videoPath) return;
+
+    const jobId = crypto.randomUUID();
+    clipJobId.current = jobId;
+    setState({ status: 'processing', progress: 0 });
+
+    try {
+      const result: HighlightReelResult = await invoke('generate_highlight_reel', {
+        videoPath,
+        clips,
+        options,
+        jobId,
+      });
+
+      setState({
+        status: 'complete',
+        outputPath: result.output_directory,
+        clipCount: clips.filter(clip => clip.isSelected).length,
+        failedClips: [],
+        reelPath: result.output_path,
+      });
+    } catch (error) {
+      if (error === JOB_CANCELLED) {
+        setState({ status: 'review', clips });
+      } else {
+        setState({ status: 'error', message: String(error) });
+      }
+    } finally {
+      clipJobId.current = null;
     }
   }, [videoPath]);
 
+  const cancelClipGeneration = useCallback(async () => {
+    if (clipJobId.current) {
+      await invoke('cancel_job', { jobId: clipJobId.current });
+    }
+  }, []);
+
   const reset = useCallback(() => {
     setState({ status: 'ready' });
     setVideoPath(null);
@@ -87,8 +161,11 @@ export function useAppState() {
     setTranscriptPath,
     context,
     setContext,
+    speakers,
     analyzeClips,
     generateClips,
+    generateHighlightReel,
+    cancelClipGeneration,
     reset,
   };
 }
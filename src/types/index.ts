@@ -2,19 +2,34 @@
 export interface Clip {
   id: string;                    // UUID
   title: string;                 // AI-generated title
-  startTime: string;             // HH:MM:SS format
-  endTime: string;               // HH:MM:SS format
+  startTime: string;             // HH:MM:SS.mmm format
+  endTime: string;               // HH:MM:SS.mmm format
   isSelected: boolean;           // User can toggle
   sanitizedFilename?: string;    // Safe filename version
+  speakers?: string[];           // Speakers heard in the clip (from VTT voice tags)
+  profileId?: string;            // Export profile for this clip only
+  reframe?: Reframe;             // Vertical/square output; unset keeps the source frame
 }
 
+// Output shape for Shorts/Reels/TikTok (9:16) or square posts (1:1)
+export type AspectRatio = '9:16' | '1:1';
+
+// How the source frame is fitted into the new shape
+export type Reframe =
+  | { aspect: AspectRatio; mode: 'center' }
+  | { aspect: AspectRatio; mode: 'offset'; position: number }  // 0 = left edge, 1 = right edge
+  | { aspect: AspectRatio; mode: 'crop'; x: number; y: number; width: number; height: number }  // Source pixels
+  | { aspect: AspectRatio; mode: 'blurred_background' };
+
+export type ReframeMode = Reframe['mode'];
+
 // Application state
 export type AppState = 
   | { status: 'ready' }
   | { status: 'analyzing' }
   | { status: 'review', clips: Clip[] }
-  | { status: 'processing', progress: number }
-  | { status: 'complete', outputPath: string, clipCount: number }
+  | { status: 'processing', progress: number, details?: ClipProgress }
+  | { status: 'complete', outputPath: string, clipCount: number, failedClips: ClipResult[], reelPath?: string }
   | { status: 'error', message: string };
 
 // File inputs
@@ -24,14 +39,212 @@ export interface ProjectFiles {
   context?: string;
 }
 
+// Error returned by generate_clips / generate_transcript_from_video after cancel_job
+export const JOB_CANCELLED = 'cancelled';
+
 // Processing result from backend
 export interface ProcessingResult {
   output_directory: string;
-  clip_count: number;
+  clip_count: number;            // Clips generated successfully
+  clips: ClipResult[];           // Every selected clip, in order
+}
+
+// How one highlight reel segment leads into the next
+export type ReelTransition = 'cut' | 'crossfade' | 'dip_to_black';
+
+// Options for generate_highlight_reel
+export interface HighlightReelOptions {
+  transition: ReelTransition;
+  transition_seconds: number;    // Shortened for very short segments
+  title_cards: boolean;          // Each clip's title on a black card before it
+  title_card_seconds: number;
+  audio_crossfade: boolean;      // Blend audio across transitions instead of fading out and in
+  profile_id: string | null;     // null = default profile
+}
+
+// Result of generate_highlight_reel
+export interface HighlightReelResult {
+  output_path: string;
+  output_directory: string;
+  duration: string;              // HH:MM:SS.mmm format
+}
+
+// How one clip in a batch turned out
+export interface ClipResult {
+  id: string;
+  title: string;
+  output_path: string | null;    // Set when the clip was written
+  error: string | null;          // Set when encoding failed
+}
+
+// Per-job transcription choices
+export interface TranscriptionOptions {
+  language: string | null;       // ISO 639-1 code, null = auto-detect
+  translate: boolean;            // Produce an English transcript
+}
+
+// Result of generate_transcript_from_video
+export interface TranscriptionResult {
+  vtt_path: string;
+  language: string | null;       // Language of the transcript text
+  translated: boolean;
 }
 
 // Progress event payload
 export interface ClipProgress {
-  current: number;
+  current: number;               // Clips finished so far
   total: number;
+  active: number;                // Clips being encoded right now
+  clip_index: number;            // Clip this update is about (0-based)
+  clip_percent: number;
+  overall_percent: number;       // Weighted by clip duration
+  speed: number | null;          // Encode speed of all workers combined, as a multiple of real time
+  eta_seconds: number | null;
+}
+
+// LLM provider used for clip analysis
+export type LlmProviderKind = 'responses' | 'chat_completions' | 'openai_compatible';
+
+export interface AnalysisSettings {
+  provider: LlmProviderKind;
+  base_url: string | null;       // Empty = provider default
+  model: string;
+  reasoning_effort: string;      // minimal | low | medium | high
+  verbosity: string;             // low | medium | high
+  api_key?: string | null;       // Only for OpenAI-compatible servers that need one
+  window_minutes: number;        // Long transcripts are analyzed in windows of this size (0 = never split)
+  window_overlap_seconds: number;
+  max_clips: number;             // Upper bound on clips for the whole video
+  structured_output: boolean;    // Request JSON-schema output from OpenAI-compatible servers
+  snap_to_sentences: boolean;    // Move clip edges to the nearest sentence start and end
+  sentence_snap_seconds: number; // Furthest an edge is moved to reach one
+  pre_roll_ms: number;           // Padding added before each suggested clip
+  post_roll_ms: number;          // Padding added after each suggested clip
+}
+
+// Settings returned by get_settings (OpenAI key is fetched separately)
+// Timeouts and retry policy for API calls
+export interface NetworkSettings {
+  connect_timeout_seconds: number;
+  request_timeout_seconds: number;
+  max_retries: number;           // Retries after the first attempt (429, 5xx, dropped connections)
+  retry_base_delay_ms: number;
+  retry_max_delay_seconds: number;
+}
+
+// Speech-to-text engine
+export type TranscriptionBackendKind = 'openai_whisper' | 'whisper_cpp';
+
+// Speech-to-text settings
+export interface TranscriptionSettings {
+  backend: TranscriptionBackendKind;
+  whisper_cpp_model_path: string | null;  // GGML model file for whisper.cpp
+  whisper_cpp_threads: number;            // 0 = one per CPU core
+  chunk_minutes: number;         // Longer audio is split (on silence where possible) and transcribed in parallel
+  chunk_overlap_seconds: number;
+  max_concurrent_chunks: number;
+  glossary: string;                       // One term per line; "wrong, other wrong => Right" also fixes spellings
+  apply_glossary_replacements: boolean;
+}
+
+// reencode: always H.264; stream_copy: copy when the clip starts on a keyframe;
+// smart_render: copy, re-encoding only up to the first keyframe
+export type ExportMode = 'reencode' | 'stream_copy' | 'smart_render';
+
+export interface VideoEncoding {
+  codec: string;                 // FFmpeg encoder, e.g. libx264, prores_ks
+  preset?: string;
+  crf?: number;
+  bitrate?: string;
+  profile?: string;
+  pixel_format?: string;
+  extra_args?: string[];
+}
+
+export interface AudioEncoding {
+  codec: string;                 // FFmpeg encoder, e.g. aac, libmp3lame
+  bitrate?: string;
+  sample_rate?: number;
+  channels?: number;
+}
+
+// Named output format (H.264, HEVC, WebM, ProRes, audio only...)
+export interface ExportProfile {
+  id: string;
+  name: string;
+  extension: string;
+  video: VideoEncoding | null;   // null = audio only
+  audio: AudioEncoding | null;
+}
+
+export type SubtitleFormat = 'vtt' | 'srt';
+
+export interface ExportSettings {
+  mode: ExportMode;
+  default_profile: string;       // Used when a job or clip doesn't pick one
+  profiles: ExportProfile[];
+  max_concurrent_clips: number;  // Clips encoded at the same time
+  threads_per_clip: number;      // FFmpeg -threads per clip, 0 = FFmpeg default
+  subtitle_files: SubtitleFormat[];  // Written next to each clip
+  handle_seconds: number;        // Extra source before and after each clip, 0 = none
+  fade_in_ms: number;            // 0 = no fade
+  fade_out_ms: number;
+}
+
+export type CaptionPosition = 'bottom' | 'middle' | 'top';
+
+// Burned-in captions; sizes are for a 1080-pixel-tall frame and scale with the clip
+export interface CaptionSettings {
+  enabled: boolean;              // Default for the "Burn in captions" toggle
+  font: string;
+  font_size: number;
+  bold: boolean;
+  colour: string;                // #RRGGBB
+  highlight_colour: string;      // Word being spoken, when word timings exist
+  outline_colour: string;
+  outline_width: number;
+  position: CaptionPosition;
+  margin: number;                // Distance from the top or bottom edge
+  word_highlight: boolean;
+}
+
+export interface AppSettings {
+  analysis: AnalysisSettings;
+  network: NetworkSettings;
+  transcription: TranscriptionSettings;
+  export: ExportSettings;
+  captions: CaptionSettings;
+}
+
+// What ffprobe reports about the selected video
+export interface VideoStreamInfo {
+  codec: string;                 // e.g. h264, hevc, prores
+  profile: string | null;
+  width: number;
+  height: number;
+  frame_rate: number | null;     // Average frames per second
+  pixel_format: string | null;
+  bit_rate: number | null;
+  level: number | null;          // As ffprobe reports it, e.g. 41 for H.264 level 4.1
+  time_base: string | null;      // e.g. 1/15360
+}
+
+export interface AudioStreamInfo {
+  index: number;                 // Stream index within the file
+  codec: string;
+  channels: number;
+  channel_layout: string | null; // e.g. stereo, 5.1(side)
+  sample_rate: number | null;
+  language: string | null;
+}
+
+export interface MediaInfo {
+  path: string;
+  duration: string;              // HH:MM:SS.mmm format
+  start_time: number;
+  format_name: string;
+  size_bytes: number | null;
+  bit_rate: number | null;
+  video: VideoStreamInfo | null;
+  audio_tracks: AudioStreamInfo[];
 }